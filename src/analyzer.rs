@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use crate::{Card, Hand, HandValue};
+use crate::composition::{dealer_probabilities, Composition};
+use crate::engine::BlackjackEngine;
+use crate::rules::{HoleCard, Surrender, TableRules};
+use crate::strategy::{add_card, ActionValues, DealerOutcomes, CARD_VALUES};
+
+/// Composition-dependent expected values for one hand. `composition` is the unseen shoe: everything except the
+/// player's cards and the dealer's up-card, so a hidden hole card still belongs in it.
+/// Splits are valued as one split with each hand played independently from the same shoe, without resplitting, so
+/// `split` is only exact when `rules.max_splits` is 1 and slightly understates a pair at tables that allow resplits.
+pub fn analyze(hand: &Hand, up_card: u32, composition: &Composition, rules: &TableRules) -> ActionValues {
+    let mut analyzer: Analyzer = Analyzer::new(up_card, rules);
+    let value: HandValue = hand.value();
+
+    ActionValues {
+        stand: analyzer.stand(composition, value.total),
+        hit: analyzer.hit(composition, value.total, value.soft),
+        double: analyzer.double(composition, value.total, value.soft),
+        split: hand.can_split().then(|| analyzer.split(composition, hand[0].value())),
+        surrender: (rules.surrender != Surrender::None).then(|| analyzer.surrender(composition)),
+    }
+}
+
+/// Analyzes a hand on the table against everything the seat can't see: the shoe plus the dealer's hidden hole card.
+pub fn analyze_seat(engine: &BlackjackEngine, seat: usize, hand: usize) -> Option<ActionValues> {
+    let seat_hand: &Hand = &engine.hands().get(seat)?.get(hand)?.hand;
+    let up_card: Card = engine.dealer().up_card().ok()?;
+
+    Some(analyze(seat_hand, up_card.value(), &unseen(engine), engine.rules()))
+}
+
+/// The chance that one more card from `composition` busts `hand`, or `None` if there are no cards left.
+pub fn bust_probability(hand: &Hand, composition: &Composition) -> Option<f64> {
+    let value: HandValue = hand.value();
+    if value.bust {
+        return Some(1.0);
+    }
+
+    let busting: u32 = CARD_VALUES
+        .into_iter()
+        .filter(|card: &u32| add_card(value.total, value.soft, *card).0 > 21)
+        .map(|card: u32| composition.count(card))
+        .sum();
+
+    match composition.total() {
+        0 => None,
+        total => Some(busting as f64 / total as f64),
+    }
+}
+
+/// The bust chance of a hand on the table, drawing from the same unseen cards as `analyze_seat` so it says nothing about
+/// the hole card.
+pub fn seat_bust_probability(engine: &BlackjackEngine, seat: usize, hand: usize) -> Option<f64> {
+    bust_probability(&engine.hands().get(seat)?.get(hand)?.hand, &unseen(engine))
+}
+
+fn unseen(engine: &BlackjackEngine) -> Composition {
+    let mut cards: Vec<Card> = engine.shoe().deck().cards().clone();
+    cards.extend(engine.dealer().hand().0.iter().filter(|card: &&Card| card.is_hidden()));
+    Composition::from_cards(&cards)
+}
+
+struct Analyzer<'a> {
+    up_card: u32,
+    rules: &'a TableRules,
+    dealer: HashMap<Composition, DealerOutcomes>,
+    hit: HashMap<(Composition, u32, bool), f64>,
+}
+
+impl<'a> Analyzer<'a> {
+    fn new(up_card: u32, rules: &'a TableRules) -> Self {
+        Self { up_card, rules, dealer: HashMap::new(), hit: HashMap::new() }
+    }
+
+    fn dealer(&mut self, composition: &Composition) -> DealerOutcomes {
+        if let Some(outcomes) = self.dealer.get(composition) {
+            return *outcomes;
+        }
+        let outcomes: DealerOutcomes = dealer_probabilities(composition, self.up_card, self.rules);
+        self.dealer.insert(*composition, outcomes);
+        outcomes
+    }
+
+    /// Under ENHC with original bets only, a dealer blackjack only takes one unit of a doubled or split wager.
+    fn original_bets_refund(&mut self, composition: &Composition) -> f64 {
+        match self.rules.hole_card == HoleCard::NoHoleCard && self.rules.original_bets_only {
+            true => self.dealer(composition).blackjack(),
+            false => 0.0,
+        }
+    }
+
+    fn stand(&mut self, composition: &Composition, total: u32) -> f64 {
+        match total > 21 {
+            true => -1.0,
+            false => self.dealer(composition).stand_ev(total),
+        }
+    }
+
+    /// Draws one card and plays on optimally (hit or stand) from there.
+    fn hit(&mut self, composition: &Composition, total: u32, soft: bool) -> f64 {
+        if let Some(ev) = self.hit.get(&(*composition, total, soft)) {
+            return *ev;
+        }
+
+        let remaining: f64 = composition.total() as f64;
+        let mut ev: f64 = 0.0;
+        for value in CARD_VALUES {
+            if let Some(next) = composition.without(value) {
+                let (next_total, next_soft) = add_card(total, soft, value);
+                let outcome: f64 = match next_total > 21 {
+                    true => -1.0,
+                    false => self.stand(&next, next_total).max(self.hit(&next, next_total, next_soft)),
+                };
+                ev += composition.count(value) as f64 / remaining * outcome;
+            }
+        }
+
+        self.hit.insert((*composition, total, soft), ev);
+        ev
+    }
+
+    fn double_without_refund(&mut self, composition: &Composition, total: u32, soft: bool) -> f64 {
+        let remaining: f64 = composition.total() as f64;
+        let mut ev: f64 = 0.0;
+        for value in CARD_VALUES {
+            if let Some(next) = composition.without(value) {
+                let (next_total, _) = add_card(total, soft, value);
+                ev += composition.count(value) as f64 / remaining * 2.0 * self.stand(&next, next_total);
+            }
+        }
+        ev
+    }
+
+    fn double(&mut self, composition: &Composition, total: u32, soft: bool) -> f64 {
+        self.double_without_refund(composition, total, soft) + self.original_bets_refund(composition)
+    }
+
+    fn surrender(&mut self, composition: &Composition) -> f64 {
+        match self.rules.hole_card {
+            HoleCard::Peek => -0.5,
+            HoleCard::NoHoleCard => {
+                let blackjack: f64 = self.dealer(composition).blackjack();
+                -0.5 * (1.0 - blackjack) - blackjack
+            },
+        }
+    }
+
+    fn split(&mut self, composition: &Composition, pair: u32) -> f64 {
+        let (total, soft) = add_card(0, false, pair);
+        let remaining: f64 = composition.total() as f64;
+        let mut ev: f64 = 0.0;
+
+        for value in CARD_VALUES {
+            if let Some(next) = composition.without(value) {
+                let (next_total, next_soft) = add_card(total, soft, value);
+                let outcome: f64 = match pair == 11 {
+                    true => self.stand(&next, next_total),
+                    false => {
+                        let mut best: f64 = self.stand(&next, next_total).max(self.hit(&next, next_total, next_soft));
+                        if self.rules.double_after_split {
+                            best = best.max(self.double_without_refund(&next, next_total, next_soft));
+                        }
+                        best
+                    },
+                };
+                ev += composition.count(value) as f64 / remaining * outcome;
+            }
+        }
+
+        2.0 * ev + self.original_bets_refund(composition)
+    }
+}
@@ -0,0 +1,110 @@
+use crate::house_edge::house_edge;
+use crate::rules::TableRules;
+use crate::simulation::SimulationStats;
+
+/// The win rate and spread of one round, in betting units. Everything else in this module follows from these
+/// two numbers using the usual diffusion approximations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskProfile {
+    pub mean: f64,
+    pub standard_deviation: f64,
+    /// Chips per unit, for reporting in chips.
+    pub unit: u32,
+}
+
+impl RiskProfile {
+    /// Standard deviation of a flat-bet round of basic strategy, close enough for most rule sets.
+    pub const FLAT_STANDARD_DEVIATION: f64 = 1.15;
+
+    pub fn from_stats(stats: &SimulationStats) -> Self {
+        Self { mean: stats.mean(), standard_deviation: stats.standard_deviation(), unit: stats.unit.max(1) }
+    }
+
+    /// Flat bets of `unit` with perfect basic strategy, using the infinite-deck house edge.
+    pub fn flat(rules: &TableRules, unit: u32) -> Self {
+        Self { mean: -house_edge(rules), standard_deviation: Self::FLAT_STANDARD_DEVIATION, unit: unit.max(1) }
+    }
+
+    fn variance(&self) -> f64 {
+        self.standard_deviation * self.standard_deviation
+    }
+
+    /// Chance of ever losing a bankroll of `bankroll` chips when playing forever.
+    pub fn risk_of_ruin(&self, bankroll: u32) -> f64 {
+        if self.mean <= 0.0 {
+            return 1.0;
+        }
+        let units: f64 = bankroll as f64 / self.unit as f64;
+        (-2.0 * self.mean * units / self.variance()).exp().min(1.0)
+    }
+
+    /// The bankroll in chips that keeps the risk of ruin at `risk`, or `None` without an edge.
+    pub fn required_bankroll(&self, risk: f64) -> Option<u32> {
+        if self.mean <= 0.0 || risk <= 0.0 || risk >= 1.0 {
+            return None;
+        }
+        let units: f64 = -risk.ln() * self.variance() / (2.0 * self.mean);
+        Some((units * self.unit as f64).ceil() as u32)
+    }
+
+    /// Rounds needed for the expected win to equal one standard deviation of the result.
+    pub fn n0(&self) -> Option<f64> {
+        match self.mean > 0.0 {
+            true => Some(self.variance() / (self.mean * self.mean)),
+            false => None,
+        }
+    }
+
+    /// Expected win per 100 rounds for a 10,000 bankroll bet at full Kelly, which makes games with different spreads
+    /// comparable. Zero without an edge.
+    pub fn score(&self) -> f64 {
+        match self.mean > 0.0 {
+            true => 1_000_000.0 * self.mean * self.mean / self.variance(),
+            false => 0.0,
+        }
+    }
+
+    /// Expected win per hour in chips.
+    pub fn hourly_win(&self, rounds_per_hour: u32) -> f64 {
+        self.mean * self.unit as f64 * rounds_per_hour as f64
+    }
+
+    /// Standard deviation of an hour's result in chips.
+    pub fn hourly_standard_deviation(&self, rounds_per_hour: u32) -> f64 {
+        self.standard_deviation * self.unit as f64 * (rounds_per_hour as f64).sqrt()
+    }
+}
+
+/// Everything `RiskProfile` can tell about playing it with a given bankroll.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BankrollReport {
+    pub profile: RiskProfile,
+    pub bankroll: u32,
+    pub rounds_per_hour: u32,
+    pub target_risk: f64,
+}
+
+impl std::fmt::Display for BankrollReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let profile: &RiskProfile = &self.profile;
+
+        writeln!(f, "Win rate:\t\t{:+.4} units per round (standard deviation {:.3})", profile.mean, profile.standard_deviation)?;
+        writeln!(
+            f,
+            "Hourly:\t\t\t{:+.2} per hour (standard deviation {:.2}) at {} rounds per hour",
+            profile.hourly_win(self.rounds_per_hour),
+            profile.hourly_standard_deviation(self.rounds_per_hour),
+            self.rounds_per_hour
+        )?;
+        match profile.n0() {
+            Some(n0) => writeln!(f, "N0:\t\t\t{:.0} rounds ({:.1} hours)", n0, n0 / self.rounds_per_hour.max(1) as f64)?,
+            None => writeln!(f, "N0:\t\t\tnever, the house has the edge")?,
+        }
+        writeln!(f, "SCORE:\t\t\t{:.2} per 100 rounds", profile.score())?;
+        writeln!(f, "Risk of ruin:\t\t{:.2}% with a bankroll of {}", profile.risk_of_ruin(self.bankroll) * 100.0, self.bankroll)?;
+        match profile.required_bankroll(self.target_risk) {
+            Some(bankroll) => write!(f, "Required bankroll:\t{} for a {:.1}% risk of ruin", bankroll, self.target_risk * 100.0),
+            None => write!(f, "Required bankroll:\tno bankroll is enough without an edge"),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::controller::hi_lo_true_count;
+use crate::engine::BlackjackEngine;
+use crate::house_edge::house_edge;
+
+/// Sizes a seat's wager at the start of each round. Progressions read the seat's previous result from
+/// `BlackjackEngine::last_result`; the caller caps the bet at the seat's balance. Asking again in the same round, after a
+/// rejected bet or for a hint, returns the same bet.
+pub trait BettingStrategy {
+    fn name(&self) -> String;
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32;
+}
+
+/// The seat's previous result the first time a progression asks in a round, and `None` on every later call.
+fn new_result(engine: &BlackjackEngine, seat: usize, observed: &mut u64) -> Option<i64> {
+    match engine.round() == *observed {
+        true => None,
+        false => {
+            *observed = engine.round();
+            engine.last_result(seat)
+        },
+    }
+}
+
+/// Every strategy with `unit` as its base bet, in the order they are offered.
+pub fn betting_strategies(unit: u32) -> Vec<Box<dyn BettingStrategy>> {
+    vec![
+        Box::new(FlatBet(unit)),
+        Box::new(Martingale::new(unit)),
+        Box::new(Paroli::new(unit)),
+        Box::new(OneThreeTwoSix::new(unit)),
+        Box::new(DAlembert::new(unit)),
+        Box::new(BetRamp::parse(unit, "1,2,4,8,12").unwrap_or(BetRamp::flat(unit))),
+        Box::new(Kelly::new(unit, 0.5)),
+    ]
+}
+
+/// Finds a strategy by its full name, ignoring case, spaces, dashes, apostrophes and anything in brackets (`martingale`,
+/// `1326`, `dalembert`, `count ramp`, `kelly`).
+pub fn betting_strategy_by_name(name: &str, unit: u32) -> Option<Box<dyn BettingStrategy>> {
+    let normalize = |name: &str| name.split('(').next().unwrap_or("").to_lowercase().replace([' ', '-', '\''], "");
+    betting_strategies(unit)
+        .into_iter()
+        .find(|strategy| normalize(&strategy.name()) == normalize(name))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlatBet(pub u32);
+
+impl BettingStrategy for FlatBet {
+    fn name(&self) -> String {
+        String::from("Flat")
+    }
+
+    fn next_bet(&mut self, _engine: &BlackjackEngine, _seat: usize) -> u32 {
+        self.0
+    }
+}
+
+/// Doubles the bet after every loss and drops back to one unit after a win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Martingale {
+    unit: u32,
+    bet: u32,
+    round: u64,
+}
+
+impl Martingale {
+    pub fn new(unit: u32) -> Self {
+        Self { unit, bet: unit, round: 0 }
+    }
+}
+
+impl BettingStrategy for Martingale {
+    fn name(&self) -> String {
+        String::from("Martingale")
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32 {
+        match new_result(engine, seat, &mut self.round).map(i64::signum) {
+            Some(-1) => self.bet = self.bet.saturating_mul(2),
+            Some(1) => self.bet = self.unit,
+            _ => {},
+        }
+        self.bet
+    }
+}
+
+/// Doubles the bet after a win, up to three wins in a row, and drops back to one unit after a loss.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Paroli {
+    unit: u32,
+    wins: u32,
+    round: u64,
+}
+
+impl Paroli {
+    pub fn new(unit: u32) -> Self {
+        Self { unit, wins: 0, round: 0 }
+    }
+}
+
+impl BettingStrategy for Paroli {
+    fn name(&self) -> String {
+        String::from("Paroli")
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32 {
+        match new_result(engine, seat, &mut self.round).map(i64::signum) {
+            Some(1) => self.wins = (self.wins + 1) % 3,
+            Some(-1) => self.wins = 0,
+            _ => {},
+        }
+        self.unit << self.wins
+    }
+}
+
+/// Bets 1, 3, 2 and 6 units on consecutive wins, starting over after a loss or a completed run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OneThreeTwoSix {
+    unit: u32,
+    step: usize,
+    round: u64,
+}
+
+impl OneThreeTwoSix {
+    const STEPS: [u32; 4] = [1, 3, 2, 6];
+
+    pub fn new(unit: u32) -> Self {
+        Self { unit, step: 0, round: 0 }
+    }
+}
+
+impl BettingStrategy for OneThreeTwoSix {
+    fn name(&self) -> String {
+        String::from("1-3-2-6")
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32 {
+        match new_result(engine, seat, &mut self.round).map(i64::signum) {
+            Some(1) => self.step = (self.step + 1) % Self::STEPS.len(),
+            Some(-1) => self.step = 0,
+            _ => {},
+        }
+        self.unit * Self::STEPS[self.step]
+    }
+}
+
+/// Adds a unit after a loss and takes one away after a win, never going below one unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DAlembert {
+    unit: u32,
+    units: u32,
+    round: u64,
+}
+
+impl DAlembert {
+    pub fn new(unit: u32) -> Self {
+        Self { unit, units: 1, round: 0 }
+    }
+}
+
+impl BettingStrategy for DAlembert {
+    fn name(&self) -> String {
+        String::from("D'Alembert")
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32 {
+        match new_result(engine, seat, &mut self.round).map(i64::signum) {
+            Some(-1) => self.units += 1,
+            Some(1) => self.units = (self.units - 1).max(1),
+            _ => {},
+        }
+        self.unit * self.units
+    }
+}
+
+/// Bets `unit` times the spread entry for the floored Hi-Lo true count: the first entry below +1, the second at +1,
+/// and so on, with the last entry covering every count above it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BetRamp {
+    pub unit: u32,
+    pub spread: Vec<u32>,
+}
+
+impl BetRamp {
+    pub fn flat(unit: u32) -> Self {
+        Self { unit, spread: vec![1] }
+    }
+
+    /// Reads a spread such as `1,2,4,8`.
+    pub fn parse(unit: u32, spread: &str) -> Option<Self> {
+        let spread: Vec<u32> = spread
+            .split(',')
+            .map(|step: &str| step.trim().parse::<u32>().ok().filter(|step: &u32| *step > 0))
+            .collect::<Option<Vec<u32>>>()?;
+
+        match spread.is_empty() {
+            true => None,
+            false => Some(Self { unit, spread }),
+        }
+    }
+
+    pub fn bet(&self, true_count: f64) -> u32 {
+        let step: usize = (true_count.floor().max(0.0) as usize).min(self.spread.len() - 1);
+        self.unit * self.spread[step]
+    }
+
+    pub fn max_bet(&self) -> u32 {
+        self.unit * self.spread.iter().max().copied().unwrap_or(1)
+    }
+}
+
+impl BettingStrategy for BetRamp {
+    fn name(&self) -> String {
+        String::from("Count ramp")
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, _seat: usize) -> u32 {
+        match self.spread.len() {
+            1 => self.bet(0.0),
+            _ => self.bet(hi_lo_true_count(engine)),
+        }
+    }
+}
+
+/// Bets `fraction` of the Kelly bet for the advantage at the current Hi-Lo true count, estimated as the table's
+/// infinite-deck player edge plus half a percent per true count. Never bets less than `unit`, and bets are
+/// rounded down to whole units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Kelly {
+    unit: u32,
+    fraction: f64,
+    house_edge: Option<f64>,
+}
+
+impl Kelly {
+    /// Variance of one round of blackjack, in squared units.
+    pub const VARIANCE: f64 = 1.33;
+    pub const EDGE_PER_TRUE_COUNT: f64 = 0.005;
+
+    pub fn new(unit: u32, fraction: f64) -> Self {
+        Self { unit, fraction: fraction.clamp(0.0, 1.0), house_edge: None }
+    }
+
+    pub fn advantage(&mut self, engine: &BlackjackEngine) -> f64 {
+        let house_edge: f64 = *self.house_edge.get_or_insert_with(|| house_edge(engine.rules()));
+        Self::EDGE_PER_TRUE_COUNT * hi_lo_true_count(engine) - house_edge
+    }
+}
+
+impl BettingStrategy for Kelly {
+    fn name(&self) -> String {
+        format!("Kelly ({:.0}%)", self.fraction * 100.0)
+    }
+
+    fn next_bet(&mut self, engine: &BlackjackEngine, seat: usize) -> u32 {
+        let advantage: f64 = self.advantage(engine);
+        let balance: f64 = engine.players().0[seat].get_balance() as f64;
+        let kelly: f64 = balance * self.fraction * advantage.max(0.0) / Self::VARIANCE;
+        let unit: u32 = self.unit.max(1);
+
+        (kelly as u32 / unit * unit).max(unit)
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use crate::{Hand, HandValue};
+use crate::engine::Action;
+use crate::rules::Surrender;
+use crate::strategy::{ActionValues, HandKind, StrategyTable, CARD_VALUES};
+
+/// One cell of a printed strategy chart. The two-letter codes fall back to the second action when the first isn't allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play {
+    Hit,
+    Stand,
+    /// Double, otherwise hit.
+    Double,
+    /// Double, otherwise stand.
+    DoubleOrStand,
+    Split,
+    SurrenderOrHit,
+    SurrenderOrStand,
+    SurrenderOrSplit,
+}
+
+impl Play {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Hit => "H",
+            Self::Stand => "S",
+            Self::Double => "D",
+            Self::DoubleOrStand => "Ds",
+            Self::Split => "P",
+            Self::SurrenderOrHit => "Rh",
+            Self::SurrenderOrStand => "Rs",
+            Self::SurrenderOrSplit => "Rp",
+        }
+    }
+
+    pub fn parse(code: &str) -> Option<Self> {
+        match code.to_lowercase().as_str() {
+            "h" => Some(Self::Hit),
+            "s" => Some(Self::Stand),
+            "d" | "dh" => Some(Self::Double),
+            "ds" => Some(Self::DoubleOrStand),
+            "p" | "sp" => Some(Self::Split),
+            "r" | "rh" => Some(Self::SurrenderOrHit),
+            "rs" => Some(Self::SurrenderOrStand),
+            "rp" => Some(Self::SurrenderOrSplit),
+            _ => None,
+        }
+    }
+
+    pub fn is_split(&self) -> bool {
+        matches!(self, Self::Split | Self::SurrenderOrSplit)
+    }
+
+    /// The first legal action this cell asks for.
+    pub fn action(&self, actions: &[Action]) -> Action {
+        let preferences: &[Action] = match self {
+            Self::Hit => &[Action::Hit],
+            Self::Stand => &[Action::Stand],
+            Self::Double => &[Action::Double, Action::Hit],
+            Self::DoubleOrStand => &[Action::Double, Action::Stand],
+            Self::Split => &[Action::Split, Action::Hit],
+            Self::SurrenderOrHit => &[Action::Surrender, Action::Hit],
+            Self::SurrenderOrStand => &[Action::Surrender, Action::Stand],
+            Self::SurrenderOrSplit => &[Action::Surrender, Action::Split, Action::Hit],
+        };
+
+        preferences
+            .iter()
+            .find(|action: &&Action| actions.contains(action))
+            .copied()
+            .unwrap_or(Action::Stand)
+    }
+
+    /// The chart cell for the best play given the values of a fresh two-card hand.
+    pub fn optimal(values: &ActionValues, surrender: bool) -> Self {
+        let mut actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double];
+        if values.split.is_some() {
+            actions.push(Action::Split);
+        }
+        let without_surrender: Action = values.best(&actions);
+        if surrender {
+            actions.push(Action::Surrender);
+        }
+
+        match (values.best(&actions), without_surrender, values.best(&[Action::Hit, Action::Stand])) {
+            (Action::Surrender, Action::Split, _) => Self::SurrenderOrSplit,
+            (Action::Surrender, _, Action::Stand) => Self::SurrenderOrStand,
+            (Action::Surrender, _, _) => Self::SurrenderOrHit,
+            (Action::Split, _, _) => Self::Split,
+            (Action::Double, _, Action::Stand) => Self::DoubleOrStand,
+            (Action::Double, _, _) => Self::Double,
+            (Action::Stand, _, _) => Self::Stand,
+            _ => Self::Hit,
+        }
+    }
+}
+
+impl std::fmt::Display for Play {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// A chart cell that differs from the computed strategy, with the expected value it gives up per unit bet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deviation {
+    pub kind: HandKind,
+    pub up_card: u32,
+    pub play: Play,
+    pub optimal: Play,
+    pub cost: f64,
+}
+
+/// A hard/soft/pair strategy chart against the up-cards 2 through Ace.
+/// Pair rows only decide whether to split; any other cell in a pair row falls through to the hand's total.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Chart {
+    cells: BTreeMap<(HandKind, u32), Play>,
+}
+
+impl Chart {
+    pub fn rows() -> Vec<HandKind> {
+        (5..=21).map(HandKind::Hard)
+            .chain((13..=21).map(HandKind::Soft))
+            .chain(CARD_VALUES.into_iter().map(HandKind::Pair))
+            .collect()
+    }
+
+    pub fn from_table(table: &StrategyTable) -> Self {
+        let surrender: bool = table.rules().surrender != Surrender::None;
+        let mut cells: BTreeMap<(HandKind, u32), Play> = BTreeMap::new();
+
+        for kind in Self::rows() {
+            for up_card in CARD_VALUES {
+                if let Some(values) = table.lookup(kind, up_card) {
+                    cells.insert((kind, up_card), Play::optimal(&values, surrender));
+                }
+            }
+        }
+
+        Self { cells }
+    }
+
+    /// Reads a chart with one row per line: a label (`H16`, `S18`, `P8`, `PA`) followed by ten cells for the
+    /// up-cards 2 through Ace, separated by commas or whitespace. Header rows, blank lines and `#` comments are skipped.
+    pub fn parse(source: &str) -> std::io::Result<Self> {
+        // Spreadsheet exports often start with a byte order mark.
+        let source: &str = source.strip_prefix('\u{FEFF}').unwrap_or(source);
+        let mut cells: BTreeMap<(HandKind, u32), Play> = BTreeMap::new();
+        let invalid = |number: usize, message: String| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, message))
+        };
+
+        for (number, line) in source.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            let words: Vec<&str> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|word: &&str| !word.is_empty())
+                .collect();
+
+            let label: &str = match words.first() {
+                None => continue,
+                Some(label) if label.eq_ignore_ascii_case("hand") || *label == "2" => continue,
+                Some(label) => label,
+            };
+
+            let kind: HandKind = parse_label(label).ok_or_else(|| invalid(number, format!("unknown row '{}'", label)))?;
+            if words.len() != CARD_VALUES.len() + 1 {
+                return Err(invalid(number, format!("expected {} cells, found {}", CARD_VALUES.len(), words.len() - 1)));
+            }
+
+            for (up_card, code) in CARD_VALUES.iter().zip(&words[1..]) {
+                let play: Play = Play::parse(code).ok_or_else(|| invalid(number, format!("unknown play '{}'", code)))?;
+                cells.insert((kind, *up_card), play);
+            }
+        }
+
+        Ok(Self { cells })
+    }
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn play(&self, kind: HandKind, up_card: u32) -> Option<Play> {
+        self.cells.get(&(kind, up_card)).copied()
+    }
+
+    /// The chart's action for `hand`, or `None` if the chart has no row for it.
+    pub fn decide(&self, hand: &Hand, up_card: u32, actions: &[Action]) -> Option<Action> {
+        if hand.can_split() && actions.contains(&Action::Split) {
+            if let Some(play) = self.play(HandKind::Pair(hand[0].value()), up_card).filter(Play::is_split) {
+                return Some(play.action(actions));
+            }
+        }
+
+        let value: HandValue = hand.value();
+        let kind: HandKind = match value.soft {
+            true => HandKind::Soft(value.total),
+            false => HandKind::Hard(value.total),
+        };
+        self.play(kind, up_card).map(|play: Play| play.action(actions))
+    }
+
+    /// Every cell that differs from the computed strategy, costliest first.
+    pub fn deviations(&self, table: &StrategyTable) -> Vec<Deviation> {
+        let surrender: bool = table.rules().surrender != Surrender::None;
+        let mut deviations: Vec<Deviation> = Vec::new();
+
+        for ((kind, up_card), play) in &self.cells {
+            let values: ActionValues = match table.lookup(*kind, *up_card) {
+                Some(values) => values,
+                None => continue,
+            };
+            let optimal: Play = Play::optimal(&values, surrender);
+            let pair: bool = matches!(kind, HandKind::Pair(_));
+            if *play == optimal || (pair && !play.is_split() && !optimal.is_split()) {
+                continue;
+            }
+
+            let mut actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double];
+            if surrender {
+                actions.push(Action::Surrender);
+            }
+            // Not splitting a pair hands the decision to the total's row, so it is valued at the best play on that total.
+            let unsplit: f64 = values.value(values.best(&actions)).unwrap_or(values.stand);
+            if values.split.is_some() {
+                actions.push(Action::Split);
+            }
+            let ev = |play: Play| match pair && !play.is_split() {
+                true => unsplit,
+                false => values.value(play.action(&actions)).unwrap_or(values.stand),
+            };
+
+            deviations.push(Deviation { kind: *kind, up_card: *up_card, play: *play, optimal, cost: ev(optimal) - ev(*play) });
+        }
+
+        deviations.sort_by(|a: &Deviation, b: &Deviation| b.cost.total_cmp(&a.cost));
+        deviations
+    }
+}
+
+pub(crate) fn parse_label(label: &str) -> Option<HandKind> {
+    let label: String = label.to_uppercase();
+    let kind: char = label.chars().next()?;
+    let value: u32 = match &label[kind.len_utf8()..] {
+        "A" => 11,
+        value => value.parse::<u32>().ok()?,
+    };
+
+    match kind {
+        'H' if (4..=21).contains(&value) => Some(HandKind::Hard(value)),
+        'S' if (12..=21).contains(&value) => Some(HandKind::Soft(value)),
+        'P' if (2..=11).contains(&value) => Some(HandKind::Pair(value)),
+        _ => None,
+    }
+}
+
+pub fn row_label(kind: HandKind) -> String {
+    match kind {
+        HandKind::Hard(total) => format!("H{}", total),
+        HandKind::Soft(total) => format!("S{}", total),
+        HandKind::Pair(11) => String::from("PA"),
+        HandKind::Pair(pair) => format!("P{}", pair),
+    }
+}
+
+/// Writes the chart in the same comma-separated format `Chart::parse` reads.
+impl std::fmt::Display for Chart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Hand,2,3,4,5,6,7,8,9,10,A")?;
+        for kind in Self::rows() {
+            let cells: Vec<&str> = CARD_VALUES
+                .iter()
+                .filter_map(|up_card: &u32| self.play(kind, *up_card).map(|play: Play| play.code()))
+                .collect();
+            if cells.len() == CARD_VALUES.len() {
+                writeln!(f, "{},{}", row_label(kind), cells.join(","))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use crate::{Card, Deck};
+use crate::rules::{HoleCard, TableRules};
+use crate::strategy::{add_card, DealerOutcomes, CARD_VALUES};
+
+/// How many cards of each value are left, indexed like `CARD_VALUES` (2 through 9, tens, Aces).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Composition(pub [u32; 10]);
+
+impl Composition {
+    pub fn of(deck: &Deck) -> Self {
+        Self::from_cards(deck.cards())
+    }
+
+    pub fn from_cards(cards: &[Card]) -> Self {
+        let mut composition: Composition = Composition::default();
+        for card in cards {
+            composition.0[card.value() as usize - 2] += 1;
+        }
+        composition
+    }
+
+    pub fn decks(decks: u32) -> Self {
+        Self::of(&Deck::build(decks))
+    }
+
+    pub fn count(&self, value: u32) -> u32 {
+        self.0[value as usize - 2]
+    }
+
+    pub fn total(&self) -> u32 {
+        self.0.iter().sum()
+    }
+
+    pub fn probability(&self, value: u32) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.count(value) as f64 / total as f64,
+        }
+    }
+
+    /// The composition with one card of `value` taken out, if there is one left.
+    pub fn without(&self, value: u32) -> Option<Self> {
+        let mut composition: Composition = *self;
+        let count: &mut u32 = &mut composition.0[value as usize - 2];
+        *count = count.checked_sub(1)?;
+        Some(composition)
+    }
+}
+
+/// Exact dealer outcomes for a finite shoe, found by enumerating every drawing order with memoization.
+/// `composition` is what is left once the up-card (and any other dealt cards) are out. In peek games the
+/// hole card is conditioned on not completing a blackjack, as in `strategy::dealer_outcomes`.
+pub fn dealer_probabilities(composition: &Composition, up_card: u32, rules: &TableRules) -> DealerOutcomes {
+    let mut memo: HashMap<Composition, DealerOutcomes> = HashMap::new();
+    let (total, soft) = add_card(0, false, up_card);
+    let excluded: Option<u32> = match (rules.hole_card, up_card) {
+        (HoleCard::Peek, 10) => Some(11),
+        (HoleCard::Peek, 11) => Some(10),
+        _ => None,
+    };
+    let remaining: u32 = composition.total() - excluded.map(|value: u32| composition.count(value)).unwrap_or(0);
+
+    let mut outcomes: DealerOutcomes = DealerOutcomes::default();
+    if remaining == 0 {
+        return outcomes;
+    }
+
+    for value in CARD_VALUES {
+        if Some(value) == excluded || composition.count(value) == 0 {
+            continue;
+        }
+        let probability: f64 = composition.count(value) as f64 / remaining as f64;
+        let (next_total, next_soft) = add_card(total, soft, value);
+        let next: Composition = composition.without(value).unwrap_or(*composition);
+
+        match next_total == 21 {
+            true => outcomes.0[DealerOutcomes::BLACKJACK] += probability,
+            false => outcomes.add(&dealer_from(&next, next_total, next_soft, rules.hit_soft_17, &mut memo), probability),
+        }
+    }
+
+    outcomes
+}
+
+/// Once past the first two cards the dealer's total follows from the cards drawn, so the composition alone is the memo key.
+fn dealer_from(composition: &Composition, total: u32, soft: bool, hit_soft_17: bool, memo: &mut HashMap<Composition, DealerOutcomes>) -> DealerOutcomes {
+    let mut outcomes: DealerOutcomes = DealerOutcomes::default();
+
+    if total > 21 {
+        outcomes.0[DealerOutcomes::BUST] = 1.0;
+        return outcomes;
+    }
+    if total > 17 || (total == 17 && (!hit_soft_17 || !soft)) {
+        outcomes.0[total as usize - 17] = 1.0;
+        return outcomes;
+    }
+    if let Some(known) = memo.get(composition) {
+        return *known;
+    }
+
+    let remaining: f64 = composition.total() as f64;
+    for value in CARD_VALUES {
+        if let Some(next) = composition.without(value) {
+            let (next_total, next_soft) = add_card(total, soft, value);
+            let probability: f64 = composition.count(value) as f64 / remaining;
+            outcomes.add(&dealer_from(&next, next_total, next_soft, hit_soft_17, memo), probability);
+        }
+    }
+
+    memo.insert(*composition, outcomes);
+    outcomes
+}
@@ -0,0 +1,288 @@
+use std::collections::VecDeque;
+use rand::prelude::*;
+use crate::{Card, PlayerHand};
+use crate::betting::{BettingStrategy, FlatBet};
+use crate::engine::{Action, BlackjackEngine, Decision};
+use crate::chart::Chart;
+use crate::counting::HiLo;
+use crate::indices::IndexTable;
+use crate::strategy::StrategyTable;
+
+/// Decides for one seat at the table. Early surrender is offered through `decide_action` with `[Surrender, Decline]`,
+/// and even money through `decide_insurance`, since it is the same bet as full insurance on a natural.
+pub trait PlayerController {
+    fn name(&self) -> String;
+
+    fn decide_bet(&mut self, engine: &BlackjackEngine, seat: usize, balance: u32) -> u32;
+
+    fn decide_action(&mut self, engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) -> Action;
+
+    fn decide_insurance(&mut self, engine: &BlackjackEngine, seat: usize, max: u32) -> u32;
+
+    fn is_human(&self) -> bool {
+        false
+    }
+
+    fn decide(&mut self, engine: &BlackjackEngine, decision: &Decision) -> Action {
+        match decision {
+            Decision::Bet { seat, balance } => Action::Bet(self.decide_bet(engine, *seat, *balance)),
+            Decision::EarlySurrender { seat } => self.decide_action(engine, *seat, 0, &[Action::Surrender, Action::Decline]),
+            Decision::Insurance { seat, max } => match self.decide_insurance(engine, *seat, *max) {
+                0 => Action::Decline,
+                amount => Action::Insurance(amount),
+            },
+            Decision::EvenMoney { seat } => {
+                let max: u32 = engine.hands()[*seat][0].bet.sum() / 2;
+                match self.decide_insurance(engine, *seat, max) {
+                    0 => Action::Decline,
+                    _ => Action::EvenMoney,
+                }
+            },
+            Decision::Play { seat, hand, actions } => self.decide_action(engine, *seat, *hand, actions),
+        }
+    }
+}
+
+fn seat_hand(engine: &BlackjackEngine, seat: usize, hand: usize) -> &PlayerHand {
+    &engine.hands()[seat][hand]
+}
+
+fn up_card_value(engine: &BlackjackEngine) -> u32 {
+    engine.dealer().up_card().map(|card: Card| card.value()).unwrap_or(10)
+}
+
+pub fn hi_lo_true_count(engine: &BlackjackEngine) -> f64 {
+    engine.counter(Box::new(HiLo)).true_count_in(engine.shoe())
+}
+
+/// Picks `preferred` if it is legal, otherwise `fallback`.
+fn prefer(actions: &[Action], preferred: Action, fallback: Action) -> Action {
+    match actions.contains(&preferred) {
+        true => preferred,
+        false => fallback,
+    }
+}
+
+/// Plays the `StrategyTable` for the engine's rules, built on its first decision.
+/// With an `IndexTable` it keeps a Hi-Lo count and plays the index plays as well.
+pub struct BasicStrategyBot {
+    betting: Box<dyn BettingStrategy>,
+    table: Option<StrategyTable>,
+    indices: Option<IndexTable>,
+}
+
+impl BasicStrategyBot {
+    pub fn new(bet: u32) -> Self {
+        Self { betting: Box::new(FlatBet(bet)), table: None, indices: None }
+    }
+
+    pub fn with_betting(mut self, betting: Box<dyn BettingStrategy>) -> Self {
+        self.betting = betting;
+        self
+    }
+
+    pub fn with_indices(mut self, indices: IndexTable) -> Self {
+        self.indices = Some(indices);
+        self
+    }
+}
+
+impl PlayerController for BasicStrategyBot {
+    fn name(&self) -> String {
+        match self.indices {
+            Some(_) => String::from("Index Bot"),
+            None => String::from("Basic Strategy Bot"),
+        }
+    }
+
+    fn decide_bet(&mut self, engine: &BlackjackEngine, seat: usize, balance: u32) -> u32 {
+        let bet: u32 = self.betting.next_bet(engine, seat);
+        engine.rules().limits.clamp(bet, balance).unwrap_or(balance)
+    }
+
+    fn decide_action(&mut self, engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) -> Action {
+        let up_card: u32 = up_card_value(engine);
+        let seat_hand: &PlayerHand = seat_hand(engine, seat, hand);
+        let table: &StrategyTable = self.table.get_or_insert_with(|| StrategyTable::new(engine.rules()));
+
+        if actions.contains(&Action::Decline) {
+            let surrender: bool = table.early_surrender(&seat_hand.hand, up_card);
+            return prefer(actions, if surrender { Action::Surrender } else { Action::Decline }, Action::Decline);
+        }
+
+        match &self.indices {
+            Some(indices) => table.decide_at_count(seat_hand, up_card, actions, indices, hi_lo_true_count(engine)),
+            None => table.decide(seat_hand, up_card, actions),
+        }
+    }
+
+    fn decide_insurance(&mut self, engine: &BlackjackEngine, _seat: usize, max: u32) -> u32 {
+        match &self.indices {
+            Some(indices) if indices.take_insurance(hi_lo_true_count(engine)) => max,
+            _ => 0,
+        }
+    }
+}
+
+/// Plays an imported `Chart`, falling back to the computed strategy for hands the chart doesn't cover.
+pub struct ChartBot {
+    strategy: BasicStrategyBot,
+    chart: Chart,
+}
+
+impl ChartBot {
+    pub fn new(bet: u32, chart: Chart) -> Self {
+        Self { strategy: BasicStrategyBot::new(bet), chart }
+    }
+}
+
+impl PlayerController for ChartBot {
+    fn name(&self) -> String {
+        String::from("Chart Bot")
+    }
+
+    fn decide_bet(&mut self, engine: &BlackjackEngine, seat: usize, balance: u32) -> u32 {
+        self.strategy.decide_bet(engine, seat, balance)
+    }
+
+    fn decide_action(&mut self, engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) -> Action {
+        if !actions.contains(&Action::Decline) {
+            if let Some(action) = self.chart.decide(&seat_hand(engine, seat, hand).hand, up_card_value(engine), actions) {
+                return action;
+            }
+        }
+
+        self.strategy.decide_action(engine, seat, hand, actions)
+    }
+
+    fn decide_insurance(&mut self, engine: &BlackjackEngine, seat: usize, max: u32) -> u32 {
+        self.strategy.decide_insurance(engine, seat, max)
+    }
+}
+
+pub struct RandomBot {
+    bet: u32,
+    rng: StdRng,
+}
+
+impl RandomBot {
+    pub fn new(bet: u32, seed: u64) -> Self {
+        Self { bet, rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl PlayerController for RandomBot {
+    fn name(&self) -> String {
+        String::from("Random Bot")
+    }
+
+    fn decide_bet(&mut self, engine: &BlackjackEngine, _seat: usize, balance: u32) -> u32 {
+        let bet: u32 = self.rng.gen_range(1..=self.bet.max(1));
+        engine.rules().limits.clamp(bet, balance).unwrap_or(balance)
+    }
+
+    fn decide_action(&mut self, _engine: &BlackjackEngine, _seat: usize, _hand: usize, actions: &[Action]) -> Action {
+        *actions.choose(&mut self.rng).unwrap_or(&Action::Stand)
+    }
+
+    fn decide_insurance(&mut self, _engine: &BlackjackEngine, _seat: usize, max: u32) -> u32 {
+        match self.rng.gen_bool(0.5) {
+            true => max,
+            false => 0,
+        }
+    }
+}
+
+/// Plays a fixed list of actions, one per line: `bet <amount>`, `hit`, `stand`, `double`, `split`,
+/// `surrender`, `insurance <amount>`, `even-money` or `decline`. Blank lines and `#` comments are skipped.
+pub struct ScriptedController {
+    script: VecDeque<Action>,
+}
+
+impl ScriptedController {
+    pub fn new(script: Vec<Action>) -> Self {
+        Self { script: script.into() }
+    }
+
+    pub fn parse(source: &str) -> std::io::Result<Self> {
+        let mut script: Vec<Action> = Vec::new();
+
+        for (number, line) in source.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let command: String = words.next().unwrap_or("").to_lowercase();
+            let amount: Option<u32> = words.next().and_then(|word: &str| word.parse::<u32>().ok());
+
+            let action: Action = match (command.as_str(), amount) {
+                ("bet", Some(amount)) => Action::Bet(amount),
+                ("insurance", Some(amount)) => Action::Insurance(amount),
+                ("hit" | "h", None) => Action::Hit,
+                ("stand" | "s", None) => Action::Stand,
+                ("double" | "d", None) => Action::Double,
+                ("split" | "p", None) => Action::Split,
+                ("surrender" | "r", None) => Action::Surrender,
+                ("even-money", None) => Action::EvenMoney,
+                ("decline" | "n", None) => Action::Decline,
+                _ => return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("line {}: unknown scripted action '{}'", number + 1, line),
+                )),
+            };
+            script.push(action);
+        }
+
+        Ok(Self::new(script))
+    }
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.script.len()
+    }
+
+    /// Takes the next scripted action if it answers this decision, leaving it for a later one otherwise.
+    fn next_if(&mut self, answers: impl Fn(&Action) -> bool) -> Option<Action> {
+        match self.script.front().is_some_and(answers) {
+            true => self.script.pop_front(),
+            false => None,
+        }
+    }
+}
+
+impl PlayerController for ScriptedController {
+    fn name(&self) -> String {
+        String::from("Scripted")
+    }
+
+    /// Scripted bets are brought within the table limits; without a bet next in the script the seat bets the minimum.
+    fn decide_bet(&mut self, engine: &BlackjackEngine, _seat: usize, balance: u32) -> u32 {
+        let bet: u32 = match self.next_if(|action: &Action| matches!(action, Action::Bet(_))) {
+            Some(Action::Bet(amount)) => amount,
+            _ => 1,
+        };
+        engine.rules().limits.clamp(bet, balance).unwrap_or(balance)
+    }
+
+    /// Without a legal play next in the script the seat stands (or declines early surrender).
+    fn decide_action(&mut self, _engine: &BlackjackEngine, _seat: usize, _hand: usize, actions: &[Action]) -> Action {
+        match self.next_if(|action: &Action| actions.contains(action)) {
+            Some(action) => action,
+            None => prefer(actions, Action::Stand, Action::Decline),
+        }
+    }
+
+    /// Without an insurance, even money or decline entry next in the script the seat declines.
+    fn decide_insurance(&mut self, _engine: &BlackjackEngine, _seat: usize, max: u32) -> u32 {
+        match self.next_if(|action: &Action| matches!(action, Action::Insurance(_) | Action::EvenMoney | Action::Decline)) {
+            Some(Action::Insurance(amount)) => amount.min(max),
+            Some(Action::EvenMoney) => max,
+            _ => 0,
+        }
+    }
+}
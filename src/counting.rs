@@ -0,0 +1,209 @@
+use crate::{Card, Hand, Rank};
+use crate::shoe::Shoe;
+
+/// Tags for the card values 2 through 9, ten-valued cards and the Ace, in that order.
+pub type TagTable = [f64; 10];
+
+pub trait CountingSystem {
+    fn name(&self) -> &'static str;
+
+    fn tags(&self) -> TagTable;
+
+    /// Unbalanced systems start below zero so the pivot lands on a fixed running count.
+    fn initial_running_count(&self, _decks: u32) -> f64 {
+        0.0
+    }
+
+    /// A balanced system counts a full deck back to zero.
+    fn is_balanced(&self) -> bool {
+        self.tags()
+            .iter()
+            .enumerate()
+            .map(|(index, tag): (usize, &f64)| tag * if index == 8 { 16.0 } else { 4.0 })
+            .sum::<f64>() == 0.0
+    }
+
+    /// Ace-neutral systems leave the Ace out of the running count and track it on the side.
+    fn is_ace_neutral(&self) -> bool {
+        self.tags()[9] == 0.0
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        self.tags()[rank.get_value() as usize - 2]
+    }
+}
+
+pub struct HiLo;
+pub struct KO;
+pub struct HiOptI;
+pub struct HiOptII;
+pub struct OmegaII;
+pub struct Zen;
+pub struct WongHalves;
+
+impl CountingSystem for HiLo {
+    fn name(&self) -> &'static str {
+        "Hi-Lo"
+    }
+
+    fn tags(&self) -> TagTable {
+        [1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, -1.0, -1.0]
+    }
+}
+
+impl CountingSystem for KO {
+    fn name(&self) -> &'static str {
+        "KO"
+    }
+
+    fn tags(&self) -> TagTable {
+        [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, -1.0, -1.0]
+    }
+
+    fn initial_running_count(&self, decks: u32) -> f64 {
+        4.0 - 4.0 * decks as f64
+    }
+}
+
+impl CountingSystem for HiOptI {
+    fn name(&self) -> &'static str {
+        "Hi-Opt I"
+    }
+
+    fn tags(&self) -> TagTable {
+        [0.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, -1.0, 0.0]
+    }
+}
+
+impl CountingSystem for HiOptII {
+    fn name(&self) -> &'static str {
+        "Hi-Opt II"
+    }
+
+    fn tags(&self) -> TagTable {
+        [1.0, 1.0, 2.0, 2.0, 1.0, 1.0, 0.0, 0.0, -2.0, 0.0]
+    }
+}
+
+impl CountingSystem for OmegaII {
+    fn name(&self) -> &'static str {
+        "Omega II"
+    }
+
+    fn tags(&self) -> TagTable {
+        [1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 0.0, -1.0, -2.0, 0.0]
+    }
+}
+
+impl CountingSystem for Zen {
+    fn name(&self) -> &'static str {
+        "Zen"
+    }
+
+    fn tags(&self) -> TagTable {
+        [1.0, 1.0, 2.0, 2.0, 2.0, 1.0, 0.0, 0.0, -2.0, -1.0]
+    }
+}
+
+impl CountingSystem for WongHalves {
+    fn name(&self) -> &'static str {
+        "Wong Halves"
+    }
+
+    fn tags(&self) -> TagTable {
+        [0.5, 1.0, 1.0, 1.5, 1.0, 0.5, 0.0, -0.5, -1.0, -1.0]
+    }
+}
+
+pub fn systems() -> Vec<Box<dyn CountingSystem>> {
+    vec![
+        Box::new(HiLo),
+        Box::new(KO),
+        Box::new(HiOptI),
+        Box::new(HiOptII),
+        Box::new(OmegaII),
+        Box::new(Zen),
+        Box::new(WongHalves),
+    ]
+}
+
+/// Finds a system by name, ignoring case, spaces and dashes (`hi-lo`, `Omega II`, `wonghalves`).
+pub fn system_by_name(name: &str) -> Option<Box<dyn CountingSystem>> {
+    let normalize = |name: &str| name.to_lowercase().replace([' ', '-'], "");
+    systems().into_iter().find(|system| normalize(system.name()) == normalize(name))
+}
+
+/// Keeps the running count and ace side count for one shoe. Hidden cards are skipped until they are turned over.
+pub struct Counter {
+    system: Box<dyn CountingSystem>,
+    decks: u32,
+    running_count: f64,
+    cards_seen: u32,
+    aces_seen: u32,
+}
+
+impl Counter {
+    pub fn new(system: Box<dyn CountingSystem>, decks: u32) -> Self {
+        let running_count: f64 = system.initial_running_count(decks);
+        Self { system, decks, running_count, cards_seen: 0, aces_seen: 0 }
+    }
+
+    pub fn system(&self) -> &dyn CountingSystem {
+        self.system.as_ref()
+    }
+
+    /// Starts over for a freshly shuffled shoe.
+    pub fn reset(&mut self) {
+        self.running_count = self.system.initial_running_count(self.decks);
+        self.cards_seen = 0;
+        self.aces_seen = 0;
+    }
+
+    pub fn count(&mut self, card: &Card) {
+        if card.is_hidden() {
+            return;
+        }
+
+        self.running_count += self.system.tag(card.rank());
+        self.cards_seen += 1;
+        if card.rank() == Rank::Ace {
+            self.aces_seen += 1;
+        }
+    }
+
+    pub fn count_cards(&mut self, cards: &[Card]) {
+        for card in cards {
+            self.count(card);
+        }
+    }
+
+    pub fn count_hand(&mut self, hand: &Hand) {
+        self.count_cards(&hand.0);
+    }
+
+    pub fn running_count(&self) -> f64 {
+        self.running_count
+    }
+
+    /// Running count per remaining deck, with the remaining decks floored at a quarter deck.
+    pub fn true_count(&self, remaining_decks: f64) -> f64 {
+        self.running_count / remaining_decks.max(0.25)
+    }
+
+    pub fn true_count_in(&self, shoe: &Shoe) -> f64 {
+        self.true_count(shoe.remaining_decks())
+    }
+
+    pub fn cards_seen(&self) -> u32 {
+        self.cards_seen
+    }
+
+    pub fn aces_seen(&self) -> u32 {
+        self.aces_seen
+    }
+
+    /// Aces left in the shoe beyond the four per remaining deck; positive means the shoe is ace-rich.
+    pub fn ace_surplus(&self, remaining_decks: f64) -> f64 {
+        (4 * self.decks) as f64 - self.aces_seen as f64 - 4.0 * remaining_decks
+    }
+}
@@ -0,0 +1,122 @@
+use crate::{Card, Deck, DeckError, Hand, HandValue, Rank};
+use crate::rules::{HoleCard, TableRules};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoleCardState {
+    /// No hole card has been dealt yet (always the case before the players act under ENHC).
+    NotDealt,
+    Hidden,
+    Revealed,
+}
+
+#[derive(Clone)]
+pub struct Dealer {
+    hand: Hand,
+    hole_card: HoleCardState,
+}
+
+impl Dealer {
+    pub fn new() -> Self {
+        Self { hand: Hand(vec![], false), hole_card: HoleCardState::NotDealt }
+    }
+
+    pub fn deal(&mut self, deck: &mut Deck, rules: &TableRules) -> Result<(), DeckError> {
+        *self = Self::new();
+        self.hand.draw_from(deck)?;
+
+        if rules.hole_card == HoleCard::Peek {
+            self.hand.draw_from_hidden(deck)?;
+            self.hole_card = HoleCardState::Hidden;
+        }
+
+        Ok(())
+    }
+
+    pub fn hand(&self) -> &Hand {
+        &self.hand
+    }
+
+    pub fn up_card(&self) -> Result<Card, DeckError> {
+        match self.hand.0.first() {
+            Some(card) => Ok(*card),
+            None => Err(DeckError::EmptyDeck),
+        }
+    }
+
+    pub fn hole_card(&self) -> HoleCardState {
+        self.hole_card
+    }
+
+    pub fn shows_ace(&self) -> bool {
+        self.up_card().is_ok_and(|card: Card| card.rank() == Rank::Ace)
+    }
+
+    /// The dealer only peeks with a hidden hole card and an Ace or ten-valued up-card.
+    pub fn can_peek(&self) -> bool {
+        self.hole_card == HoleCardState::Hidden && self.up_card().is_ok_and(|card: Card| card.value() >= 10)
+    }
+
+    /// Checks the hole card for blackjack without revealing it.
+    pub fn peek(&self) -> bool {
+        self.can_peek() && self.hand.is_blackjack()
+    }
+
+    pub fn reveal(&mut self) {
+        if self.hole_card == HoleCardState::Hidden {
+            for card in self.hand.0.iter_mut() {
+                card.reveal();
+            }
+            self.hole_card = HoleCardState::Revealed;
+        }
+    }
+
+    pub fn has_blackjack(&self) -> bool {
+        self.hole_card != HoleCardState::Hidden && self.hand.is_blackjack()
+    }
+
+    pub fn play(&mut self, deck: &mut Deck, rules: &TableRules) -> Result<&Hand, DeckError> {
+        match self.hole_card {
+            HoleCardState::NotDealt => {
+                self.hand.draw_from(deck)?;
+                self.hole_card = HoleCardState::Revealed;
+            },
+            HoleCardState::Hidden => self.reveal(),
+            HoleCardState::Revealed => {},
+        }
+
+        dealer_logic(deck, &mut self.hand, rules.hit_soft_17)?;
+        Ok(&self.hand)
+    }
+}
+
+impl Default for Dealer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Display for Dealer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards: Vec<String> = self.hand.0.iter().map(|card: &Card| card.to_string()).collect();
+        write!(f, "{}", cards.join(" "))
+    }
+}
+
+pub fn dealer_logic<'a>(deck: &'a mut Deck, dealer_hand: &'a mut Hand, hit_on_soft_17: bool) -> Result<&'a mut Hand, DeckError> {
+    loop {
+        let value: HandValue = dealer_hand.value();
+
+        if value.bust {
+            dealer_hand.1 = true;
+            break;
+        }
+
+        if value.total > 17 || (value.total == 17 && (!hit_on_soft_17 || !value.soft)) {
+            break;
+        }
+
+        dealer_hand.draw_from(deck)?;
+    }
+
+    Ok(dealer_hand)
+}
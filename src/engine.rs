@@ -0,0 +1,582 @@
+use crate::{Balance, Bet, Card, DeckError, Hand, IntoChips, PlayerHand};
+use crate::counting::{Counter, CountingSystem};
+use crate::dealer::Dealer;
+use crate::payout::{committed_bets, max_insurance, settle_round, Settlement};
+use crate::rules::{BelowMinimum, HoleCard, Surrender, TableRules};
+use crate::shoe::Shoe;
+
+#[derive(Clone, Debug)]
+pub struct Player(pub u32, pub Balance, pub Bet, pub bool);
+
+impl Player {
+    pub fn get_balance(&self) -> u32 {
+        self.1.sum()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.3
+    }
+
+    pub fn bankrupt(&mut self) {
+        self.3 = false
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Table(pub Vec<Player>);
+
+impl Table {
+    pub fn get_player_by_id(&self, id: u32) -> Option<Player> {
+        self.0.iter().find(|player: &&Player| player.0 == id).cloned()
+    }
+
+    pub fn get_mut_player_by_id(&mut self, id: u32) -> Option<&mut Player> {
+        self.0.iter_mut().find(|player: &&mut Player| player.0 == id)
+    }
+}
+
+pub fn game_over(players: &[Player]) -> bool {
+    players.iter().all(|player: &Player| !player.is_active())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Bet(u32),
+    /// Declines early surrender, insurance or even money.
+    Decline,
+    Insurance(u32),
+    EvenMoney,
+    Hit,
+    Stand,
+    Double,
+    Split,
+    Surrender,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bet(amount) => write!(f, "Bet {}", amount),
+            Self::Decline => write!(f, "Decline"),
+            Self::Insurance(amount) => write!(f, "Insurance {}", amount),
+            Self::EvenMoney => write!(f, "Even Money"),
+            Self::Hit => write!(f, "Hit"),
+            Self::Stand => write!(f, "Stand"),
+            Self::Double => write!(f, "Double-Down"),
+            Self::Split => write!(f, "Split"),
+            Self::Surrender => write!(f, "Surrender"),
+        }
+    }
+}
+
+/// The input the engine is waiting for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Decision {
+    Bet { seat: usize, balance: u32 },
+    EarlySurrender { seat: usize },
+    Insurance { seat: usize, max: u32 },
+    EvenMoney { seat: usize },
+    Play { seat: usize, hand: usize, actions: Vec<Action> },
+}
+
+impl Decision {
+    pub fn seat(&self) -> usize {
+        match self {
+            Self::Bet { seat, .. }
+            | Self::EarlySurrender { seat }
+            | Self::Insurance { seat, .. }
+            | Self::EvenMoney { seat }
+            | Self::Play { seat, .. } => *seat,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    Shuffled,
+    /// The shoe ran out mid-round and the discards were shuffled back in.
+    DiscardsShuffled,
+    RoundStarted { round: u64 },
+    BetPlaced { seat: usize, amount: u32 },
+    Dealt,
+    EarlySurrendered { seat: usize },
+    InsuranceTaken { seat: usize, amount: u32 },
+    EvenMoneyTaken { seat: usize },
+    DealerPeeked { blackjack: bool },
+    CardDrawn { seat: usize, hand: usize, card: Card },
+    Doubled { seat: usize, hand: usize, card: Card },
+    Split { seat: usize, hand: usize },
+    Stood { seat: usize, hand: usize },
+    Surrendered { seat: usize, hand: usize },
+    Busted { seat: usize, hand: usize },
+    DealerPlayed,
+    Settled { seat: usize, settlement: Settlement },
+    Bankrupt { seat: usize },
+    /// The seat's balance is below the table minimum, so it skips this round.
+    SatOut { seat: usize },
+    RoundOver { round: u64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Idle,
+    Betting(usize),
+    EarlySurrender(usize),
+    Insurance(usize),
+    Playing { seat: usize, hand: usize },
+    Finished,
+}
+
+/// A blackjack table as a state machine: callers feed it `Action`s for the pending `Decision` and render the `Event`s it returns.
+pub struct BlackjackEngine {
+    rules: TableRules,
+    shoe: Shoe,
+    players: Table,
+    dealer: Dealer,
+    hands: Vec<Vec<PlayerHand>>,
+    settlements: Vec<Vec<Settlement>>,
+    /// Each seat's net result from the last round it played, kept after `start_round` clears the settlements.
+    last_results: Vec<Option<i64>>,
+    phase: Phase,
+    round: u64,
+}
+
+impl BlackjackEngine {
+    pub fn new(rules: TableRules, shoe: Shoe, players: Table) -> Self {
+        let seats: usize = players.0.len();
+        Self {
+            rules,
+            shoe,
+            players,
+            dealer: Dealer::new(),
+            hands: vec![Vec::new(); seats],
+            settlements: vec![Vec::new(); seats],
+            last_results: vec![None; seats],
+            phase: Phase::Idle,
+            round: 0,
+        }
+    }
+
+    pub fn rules(&self) -> &TableRules {
+        &self.rules
+    }
+
+    pub fn shoe(&self) -> &Shoe {
+        &self.shoe
+    }
+
+    pub fn players(&self) -> &Table {
+        &self.players
+    }
+
+    pub fn dealer(&self) -> &Dealer {
+        &self.dealer
+    }
+
+    pub fn hands(&self) -> &[Vec<PlayerHand>] {
+        &self.hands
+    }
+
+    pub fn settlements(&self) -> &[Vec<Settlement>] {
+        &self.settlements
+    }
+
+    /// The seat's net result in the last round it played, if it has played one.
+    pub fn last_result(&self, seat: usize) -> Option<i64> {
+        self.last_results.get(seat).copied().flatten()
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn round(&self) -> u64 {
+        self.round
+    }
+
+    /// Every face-up card since the last shuffle: the discard tray and, while a round is in play, the cards on the table.
+    pub fn seen_cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = self.shoe.discards_seen().to_vec();
+
+        if matches!(self.phase, Phase::EarlySurrender(_) | Phase::Insurance(_) | Phase::Playing { .. }) {
+            cards.extend(self.dealer.hand().0.iter().filter(|card: &&Card| !card.is_hidden()));
+            for seat_hands in &self.hands {
+                for seat_hand in seat_hands {
+                    cards.extend(&seat_hand.hand.0);
+                }
+            }
+        }
+
+        cards
+    }
+
+    /// A counter for `system` that has seen everything in `seen_cards`.
+    pub fn counter(&self, system: Box<dyn CountingSystem>) -> Counter {
+        let mut counter: Counter = Counter::new(system, self.rules.decks);
+        counter.count_cards(&self.seen_cards());
+        counter
+    }
+
+    /// Sets a seat's balance between rounds, bringing a bankrupt seat back into play.
+    pub fn rebuy(&mut self, seat: usize, amount: u32) -> Result<(), DeckError> {
+        if !matches!(self.phase, Phase::Idle | Phase::Finished) {
+            return Err(DeckError::IllegalAction);
+        }
+
+        let player: &mut Player = self.players.0.get_mut(seat).ok_or(DeckError::IllegalAction)?;
+        player.1 = Balance(amount.into_chips());
+        player.3 = true;
+        Ok(())
+    }
+
+    /// True once no seat can cover the table minimum.
+    pub fn is_game_over(&self) -> bool {
+        game_over(&self.players.0) || !(0..self.players.0.len()).any(|seat: usize| self.can_bet(seat))
+    }
+
+    /// Whether the seat is in play and its balance covers the table minimum.
+    pub fn can_bet(&self, seat: usize) -> bool {
+        self.players.0.get(seat).is_some_and(|player: &Player| {
+            player.is_active() && self.rules.limits.clamp(self.rules.limits.minimum, player.get_balance()).is_some()
+        })
+    }
+
+    pub fn start_round(&mut self) -> Result<Vec<Event>, DeckError> {
+        if !matches!(self.phase, Phase::Idle | Phase::Finished) || self.is_game_over() {
+            return Err(DeckError::IllegalAction);
+        }
+
+        let mut events: Vec<Event> = Vec::new();
+
+        if self.shoe.cut_card_reached() {
+            self.shoe.shuffle();
+            events.push(Event::Shuffled);
+        }
+
+        for player in self.players.0.iter_mut() {
+            player.2 = Bet(vec![]);
+        }
+        for seat_hands in self.hands.iter_mut() {
+            seat_hands.clear();
+        }
+        for seat_settlements in self.settlements.iter_mut() {
+            seat_settlements.clear();
+        }
+
+        self.round += 1;
+        events.push(Event::RoundStarted { round: self.round });
+
+        self.retire_short_stacks(&mut events);
+        for seat in 0..self.players.0.len() {
+            if self.players.0[seat].is_active() && !self.can_bet(seat) {
+                events.push(Event::SatOut { seat });
+            }
+        }
+
+        match self.next_seat(0, Self::can_bet) {
+            Some(seat) => self.phase = Phase::Betting(seat),
+            None => self.deal(&mut events)?,
+        }
+
+        Ok(events)
+    }
+
+    pub fn decision(&self) -> Option<Decision> {
+        match self.phase {
+            Phase::Betting(seat) => Some(Decision::Bet { seat, balance: self.players.0[seat].get_balance() }),
+            Phase::EarlySurrender(seat) => Some(Decision::EarlySurrender { seat }),
+            Phase::Insurance(seat) => match self.hands[seat][0].is_natural() {
+                true => Some(Decision::EvenMoney { seat }),
+                false => Some(Decision::Insurance { seat, max: self.insurance_limit(seat) }),
+            },
+            Phase::Playing { seat, hand } => Some(Decision::Play { seat, hand, actions: self.legal_actions(seat, hand) }),
+            Phase::Idle | Phase::Finished => None,
+        }
+    }
+
+    pub fn legal_actions(&self, seat: usize, hand: usize) -> Vec<Action> {
+        let seat_hand: &PlayerHand = &self.hands[seat][hand];
+        let mut actions: Vec<Action> = vec![Action::Hit, Action::Stand];
+
+        if seat_hand.hand.0.len() != 2 {
+            return actions;
+        }
+
+        let splits: u32 = self.hands[seat].len() as u32 - 1;
+        let can_afford: bool = committed_bets(&self.hands[seat]) + seat_hand.bet.sum() <= self.players.0[seat].get_balance();
+
+        if can_afford && (!seat_hand.split || self.rules.double_after_split) {
+            actions.push(Action::Double);
+        }
+        if can_afford && seat_hand.hand.can_split() && splits < self.rules.max_splits {
+            actions.push(Action::Split);
+        }
+        if self.rules.surrender != Surrender::None && seat_hand.can_surrender() {
+            actions.push(Action::Surrender);
+        }
+
+        actions
+    }
+
+    pub fn apply(&mut self, action: Action) -> Result<Vec<Event>, DeckError> {
+        let mut events: Vec<Event> = Vec::new();
+
+        match (self.phase, action) {
+            (Phase::Betting(seat), Action::Bet(amount)) => {
+                self.rules.limits.validate(amount, self.players.0[seat].get_balance())?;
+                self.players.0[seat].2 = Bet(amount.into_chips());
+                events.push(Event::BetPlaced { seat, amount });
+
+                match self.next_seat(seat + 1, Self::can_bet) {
+                    Some(next) => self.phase = Phase::Betting(next),
+                    None => self.deal(&mut events)?,
+                }
+            },
+            (Phase::EarlySurrender(seat), Action::Surrender | Action::Decline) => {
+                if action == Action::Surrender {
+                    self.hands[seat][0].surrendered = true;
+                    events.push(Event::EarlySurrendered { seat });
+                }
+                self.early_surrender_from(seat + 1, &mut events)?;
+            },
+            (Phase::Insurance(seat), Action::Insurance(amount)) if !self.hands[seat][0].is_natural() => {
+                if amount > self.insurance_limit(seat) {
+                    return Err(DeckError::InsufficientBalance);
+                }
+                if amount > 0 {
+                    self.hands[seat][0].insurance = amount;
+                    events.push(Event::InsuranceTaken { seat, amount });
+                }
+                self.insurance_from(seat + 1, &mut events)?;
+            },
+            (Phase::Insurance(seat), Action::EvenMoney) if self.hands[seat][0].is_natural() => {
+                self.hands[seat][0].even_money = true;
+                events.push(Event::EvenMoneyTaken { seat });
+                self.insurance_from(seat + 1, &mut events)?;
+            },
+            (Phase::Insurance(seat), Action::Decline) => self.insurance_from(seat + 1, &mut events)?,
+            (Phase::Playing { seat, hand }, _) => {
+                if !self.legal_actions(seat, hand).contains(&action) {
+                    return Err(DeckError::IllegalAction);
+                }
+                self.play(seat, hand, action, &mut events)?;
+            },
+            _ => return Err(DeckError::IllegalAction),
+        }
+
+        Ok(events)
+    }
+
+    fn play(&mut self, seat: usize, hand: usize, action: Action, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        match action {
+            Action::Hit => {
+                self.reserve(1, events);
+                let card: Card = self.hands[seat][hand].hand.draw_from(self.shoe.deck_mut())?;
+                events.push(Event::CardDrawn { seat, hand, card });
+
+                let seat_hand: &mut PlayerHand = &mut self.hands[seat][hand];
+                if seat_hand.hand.value().bust {
+                    seat_hand.hand.1 = true;
+                    events.push(Event::Busted { seat, hand });
+                } else if seat_hand.hand.value().total < 21 {
+                    return Ok(());
+                }
+            },
+            Action::Double => {
+                self.hands[seat][hand].double_down();
+                self.reserve(1, events);
+                let card: Card = self.hands[seat][hand].hand.draw_from(self.shoe.deck_mut())?;
+                events.push(Event::Doubled { seat, hand, card });
+
+                let seat_hand: &mut PlayerHand = &mut self.hands[seat][hand];
+                if seat_hand.hand.value().bust {
+                    seat_hand.hand.1 = true;
+                    events.push(Event::Busted { seat, hand });
+                }
+            },
+            Action::Split => {
+                let seat_hand: &mut PlayerHand = &mut self.hands[seat][hand];
+                let mut new_hand: PlayerHand = PlayerHand::new(seat_hand.hand.split()?, seat_hand.bet.clone());
+                new_hand.split = true;
+                seat_hand.split = true;
+                self.hands[seat].insert(hand + 1, new_hand);
+                events.push(Event::Split { seat, hand });
+            },
+            Action::Surrender => {
+                self.hands[seat][hand].surrendered = true;
+                events.push(Event::Surrendered { seat, hand });
+            },
+            Action::Stand => events.push(Event::Stood { seat, hand }),
+            _ => return Err(DeckError::IllegalAction),
+        }
+
+        match action {
+            Action::Split => self.turn_from(seat, hand, events),
+            _ => self.turn_from(seat, hand + 1, events),
+        }
+    }
+
+    fn insurance_limit(&self, seat: usize) -> u32 {
+        let available: u32 = self.players.0[seat].get_balance() - committed_bets(&self.hands[seat]);
+        max_insurance(&self.hands[seat][0]).min(available)
+    }
+
+    /// Seats that can no longer cover the table minimum go bankrupt, unless the table lets them sit out.
+    fn retire_short_stacks(&mut self, events: &mut Vec<Event>) {
+        for seat in 0..self.players.0.len() {
+            let balance: u32 = self.players.0[seat].get_balance();
+            let sits_out: bool = self.rules.below_minimum == BelowMinimum::SitOut && balance > 0;
+
+            if self.players.0[seat].is_active() && !self.can_bet(seat) && !sits_out {
+                self.players.0[seat].bankrupt();
+                events.push(Event::Bankrupt { seat });
+            }
+        }
+    }
+
+    fn next_seat(&self, from: usize, eligible: impl Fn(&Self, usize) -> bool) -> Option<usize> {
+        (from..self.players.0.len()).find(|seat: &usize| self.players.0[*seat].is_active() && eligible(self, *seat))
+    }
+
+    /// Shuffles the discards back in if fewer than `cards` are left to draw.
+    fn reserve(&mut self, cards: u32, events: &mut Vec<Event>) {
+        if self.shoe.remaining() < cards && self.shoe.reshuffle_discards() {
+            events.push(Event::DiscardsShuffled);
+        }
+    }
+
+    fn deal(&mut self, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        let in_round = |player: &Player| player.is_active() && player.2.sum() > 0;
+        let seats: u32 = self.players.0.iter().filter(|player: &&Player| in_round(player)).count() as u32;
+        let dealer_cards: u32 = if self.rules.hole_card == HoleCard::Peek { 2 } else { 1 };
+        self.reserve(dealer_cards + 2 * seats, events);
+
+        self.dealer.deal(self.shoe.deck_mut(), &self.rules)?;
+
+        for seat in 0..self.players.0.len() {
+            if in_round(&self.players.0[seat]) {
+                let bet: Bet = self.players.0[seat].2.clone();
+                let hand: Hand = self.shoe.deck_mut().deal_hand(2)?;
+                self.hands[seat] = vec![PlayerHand::new(hand, bet)];
+            }
+        }
+        events.push(Event::Dealt);
+
+        match self.rules.surrender == Surrender::Early && self.dealer.can_peek() {
+            true => self.early_surrender_from(0, events),
+            false => self.insurance_from(0, events),
+        }
+    }
+
+    fn early_surrender_from(&mut self, from: usize, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        match self.next_seat(from, |engine: &Self, seat: usize| !engine.hands[seat].is_empty() && !engine.hands[seat][0].is_natural()) {
+            Some(seat) => {
+                self.phase = Phase::EarlySurrender(seat);
+                Ok(())
+            },
+            None => self.insurance_from(0, events),
+        }
+    }
+
+    fn insurance_from(&mut self, from: usize, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        let offered: bool = self.rules.insurance && self.dealer.shows_ace();
+        let eligible = |engine: &Self, seat: usize| {
+            let seat_hand: &PlayerHand = match engine.hands[seat].first() {
+                Some(seat_hand) => seat_hand,
+                None => return false,
+            };
+            !seat_hand.surrendered && (seat_hand.is_natural() || engine.insurance_limit(seat) > 0)
+        };
+
+        match offered.then(|| self.next_seat(from, eligible)).flatten() {
+            Some(seat) => {
+                self.phase = Phase::Insurance(seat);
+                Ok(())
+            },
+            None => self.peek(events),
+        }
+    }
+
+    fn peek(&mut self, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        if self.dealer.can_peek() {
+            let blackjack: bool = self.dealer.peek();
+            events.push(Event::DealerPeeked { blackjack });
+
+            if blackjack {
+                return self.dealer_turn(events);
+            }
+        }
+
+        self.turn_from(0, 0, events)
+    }
+
+    /// Moves the turn to the first hand at or after `(seat, hand)` that still needs a decision.
+    fn turn_from(&mut self, mut seat: usize, mut hand: usize, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        while seat < self.hands.len() {
+            if hand >= self.hands[seat].len() {
+                seat += 1;
+                hand = 0;
+                continue;
+            }
+
+            // A hand created by a split only holds one card until it is played.
+            if self.hands[seat][hand].hand.0.len() == 1 {
+                self.reserve(1, events);
+                let card: Card = self.hands[seat][hand].hand.draw_from(self.shoe.deck_mut())?;
+                events.push(Event::CardDrawn { seat, hand, card });
+
+                if self.hands[seat][hand].is_split_aces() {
+                    hand += 1;
+                    continue;
+                }
+            }
+
+            let seat_hand: &PlayerHand = &self.hands[seat][hand];
+            if seat_hand.surrendered || seat_hand.even_money || seat_hand.hand.busted() || seat_hand.hand.value().total >= 21 {
+                hand += 1;
+                continue;
+            }
+
+            self.phase = Phase::Playing { seat, hand };
+            return Ok(());
+        }
+
+        self.dealer_turn(events)
+    }
+
+    fn dealer_turn(&mut self, events: &mut Vec<Event>) -> Result<(), DeckError> {
+        // The dealer's draws can't be counted in advance, so the discards go back in whenever the shoe runs dry.
+        loop {
+            match self.dealer.play(self.shoe.deck_mut(), &self.rules).map(|_| ()) {
+                Err(DeckError::EmptyDeck) if self.shoe.reshuffle_discards() => events.push(Event::DiscardsShuffled),
+                result => break result?,
+            }
+        }
+        events.push(Event::DealerPlayed);
+
+        self.settlements = settle_round(&self.hands, self.dealer.hand(), &self.rules);
+
+        for (seat, seat_settlements) in self.settlements.iter().enumerate() {
+            for settlement in seat_settlements {
+                settlement.apply(&mut self.players.0[seat].1);
+                events.push(Event::Settled { seat, settlement: *settlement });
+            }
+            if !seat_settlements.is_empty() {
+                self.last_results[seat] = Some(seat_settlements.iter().map(|settlement: &Settlement| settlement.net).sum());
+            }
+        }
+
+        self.retire_short_stacks(events);
+
+        for seat_hands in &self.hands {
+            for seat_hand in seat_hands {
+                self.shoe.discard(&seat_hand.hand);
+            }
+        }
+        self.shoe.discard(self.dealer.hand());
+
+        self.phase = Phase::Finished;
+        events.push(Event::RoundOver { round: self.round });
+        Ok(())
+    }
+}
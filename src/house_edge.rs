@@ -0,0 +1,137 @@
+use crate::engine::Action;
+use crate::rules::{HoleCard, Surrender, TableRules};
+use crate::strategy::{add_card, card_probability, ActionValues, HandKind, StrategyTable, CARD_VALUES};
+
+/// The house edge of perfect basic strategy in an infinite deck, found by weighing every starting hand against every
+/// up-card. Splits are valued without resplitting and insurance is never taken, as in `StrategyTable`.
+pub fn house_edge(rules: &TableRules) -> f64 {
+    let table: StrategyTable = StrategyTable::new(rules);
+    let (numerator, denominator) = rules.blackjack_payout.ratio();
+    let natural: f64 = numerator as f64 / denominator as f64;
+
+    let mut actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double, Action::Split];
+    if rules.surrender != Surrender::None {
+        actions.push(Action::Surrender);
+    }
+
+    let mut ev: f64 = 0.0;
+    for up_card in CARD_VALUES {
+        let blackjack: f64 = match up_card {
+            11 => card_probability(10),
+            10 => card_probability(11),
+            _ => 0.0,
+        };
+
+        for first in CARD_VALUES {
+            for second in CARD_VALUES {
+                let probability: f64 = card_probability(up_card) * card_probability(first) * card_probability(second);
+                let (total, soft) = add_card(first, first == 11, second);
+
+                if total == 21 {
+                    ev += probability * (1.0 - blackjack) * natural;
+                    continue;
+                }
+
+                let kind: HandKind = match (first == second, soft) {
+                    (true, _) => HandKind::Pair(first),
+                    (false, true) => HandKind::Soft(total),
+                    (false, false) => HandKind::Hard(total),
+                };
+                let values: ActionValues = match table.lookup(kind, up_card) {
+                    Some(values) => values,
+                    None => continue,
+                };
+                let best: f64 = values.value(values.best(&actions)).unwrap_or(values.stand);
+
+                // Peek values are conditioned on the dealer not having blackjack; without a hole card they already include it.
+                let mut hand: f64 = match rules.hole_card {
+                    HoleCard::Peek => -blackjack + (1.0 - blackjack) * best,
+                    HoleCard::NoHoleCard => best,
+                };
+                if rules.surrender == Surrender::Early {
+                    hand = hand.max(-0.5);
+                }
+                ev += probability * hand;
+            }
+        }
+    }
+
+    -ev
+}
+
+/// What dealing from `decks` decks instead of an infinite deck adds to the house edge (always negative), using the usual
+/// per-deck adjustment: `a / decks + b / decks²`, fitted to the published deck-count effects of one, two, four, five and six
+/// decks against eight (0.48%, 0.19%, 0.06%, 0.03% and 0.02% for the player).
+pub fn deck_count_effect(decks: u32) -> f64 {
+    const PER_DECK: f64 = 0.00454;
+    const PER_DECK_SQUARED: f64 = 0.00084;
+
+    let decks: f64 = decks.max(1) as f64;
+    -(PER_DECK / decks + PER_DECK_SQUARED / (decks * decks))
+}
+
+/// What one rule adds to (or, when negative, takes off) the house edge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleContribution {
+    pub rule: String,
+    pub edge: f64,
+}
+
+/// Copies one rule from the second rule set into the first.
+type RuleSwitch = fn(&mut TableRules, &TableRules);
+
+/// Breaks the house edge down rule by rule. The first entry is the infinite-deck Vegas Strip base game (S17, 3:2, DAS, no
+/// surrender, peek); each following entry is the change from switching one more rule to the table's, and the last is
+/// `deck_count_effect`, so the entries sum to `house_edge(rules) + deck_count_effect(rules.decks)`. Resplits aren't modelled.
+pub fn rule_contributions(rules: &TableRules) -> Vec<RuleContribution> {
+    let hole_card: &str = match (rules.hole_card, rules.original_bets_only) {
+        (HoleCard::Peek, _) => "Dealer peeks",
+        (HoleCard::NoHoleCard, true) => "No hole card (original bets only)",
+        (HoleCard::NoHoleCard, false) => "No hole card (all bets lost)",
+    };
+    let steps: Vec<(String, RuleSwitch)> = vec![
+        (
+            String::from(if rules.hit_soft_17 { "Dealer hits soft 17" } else { "Dealer stands on soft 17" }),
+            |current: &mut TableRules, rules: &TableRules| current.hit_soft_17 = rules.hit_soft_17,
+        ),
+        (
+            format!("Blackjack pays {}", rules.blackjack_payout),
+            |current: &mut TableRules, rules: &TableRules| current.blackjack_payout = rules.blackjack_payout,
+        ),
+        (
+            String::from(if rules.double_after_split { "Double after split" } else { "No double after split" }),
+            |current: &mut TableRules, rules: &TableRules| current.double_after_split = rules.double_after_split,
+        ),
+        (
+            format!("Surrender: {}", rules.surrender),
+            |current: &mut TableRules, rules: &TableRules| current.surrender = rules.surrender,
+        ),
+        (
+            String::from(hole_card),
+            |current: &mut TableRules, rules: &TableRules| {
+                current.hole_card = rules.hole_card;
+                current.original_bets_only = rules.original_bets_only;
+            },
+        ),
+    ];
+
+    let mut current: TableRules = TableRules::vegas_strip();
+    let mut edge: f64 = house_edge(&current);
+    let mut contributions: Vec<RuleContribution> = vec![RuleContribution { rule: String::from("Base game (S17, 3:2, DAS, no surrender, peek)"), edge }];
+
+    for (rule, switch) in steps {
+        let mut next: TableRules = current.clone();
+        switch(&mut next, rules);
+        if next == current {
+            continue;
+        }
+
+        let next_edge: f64 = house_edge(&next);
+        contributions.push(RuleContribution { rule, edge: next_edge - edge });
+        current = next;
+        edge = next_edge;
+    }
+
+    contributions.push(RuleContribution { rule: format!("{} deck(s)", rules.decks), edge: deck_count_effect(rules.decks) });
+    contributions
+}
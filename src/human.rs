@@ -0,0 +1,245 @@
+use std::io;
+use deckbuilder::prelude::*;
+
+/// A seat played by someone typing at the terminal. With a betting strategy, each bet prompt offers its bet as a suggestion.
+#[derive(Default)]
+pub struct StdinController {
+    suggestion: Option<Box<dyn BettingStrategy>>,
+}
+
+impl StdinController {
+    pub fn new(suggestion: Option<Box<dyn BettingStrategy>>) -> Self {
+        Self { suggestion }
+    }
+}
+
+pub fn read_response() -> String {
+    let mut response: String = String::new();
+
+    io::stdin()
+        .read_line(&mut response)
+        .expect("Failed to read line");
+
+    response.trim().to_string()
+}
+
+fn prompt_bet(balance: u32, limits: &TableLimits, suggestion: Option<u32>) -> Bet {
+    let mut current_bet: Bet = Bet(vec![]);
+    println!("Table limits: {}", limits);
+
+    loop {
+        match suggestion {
+            Some(bet) => println!("'1', '5', '10', '25', '100', '500', '1000', 'All-In', 'Suggested ({})', 'Clear', 'Ok'", bet),
+            None => println!("'1', '5', '10', '25', '100', '500', '1000', 'All-In', 'Clear', 'Ok'"),
+        }
+        println!("Current bet: \x1b[1;32m{}\x1b[0m", current_bet.sum());
+
+        match read_response().as_str() {
+            "1" => current_bet.0.push(Chip::C1),
+            "5" => current_bet.0.push(Chip::C5),
+            "10" => current_bet.0.push(Chip::C10),
+            "25" => current_bet.0.push(Chip::C25),
+            "100" => current_bet.0.push(Chip::C100),
+            "500" => current_bet.0.push(Chip::C500),
+            "1000" => current_bet.0.push(Chip::C1000),
+            "All-In" | "A" | "a" => {
+                let bet: u32 = limits.clamp(balance, balance).unwrap_or(balance);
+                if bet < balance {
+                    println!("The table allows at most {} here.", bet);
+                }
+                current_bet.0 = bet.into_chips();
+                break;
+            },
+            "Suggested" | "S" | "s" if suggestion.is_some() => {
+                current_bet.0 = suggestion.unwrap_or(0).into_chips();
+                break;
+            },
+            "Clear" | "C" | "c" => {
+                current_bet.0.clear();
+                continue;
+            },
+            "Ok" | "ok" | "o" => {
+                match limits.validate(current_bet.sum(), balance) {
+                    Ok(()) => break,
+                    Err(e) => {
+                        println!("Invalid bet: {}.", e);
+                        continue;
+                    },
+                }
+            },
+            _ => {
+                println!("You must place a bet!");
+                continue;
+            },
+        }
+
+        if current_bet.sum() > balance {
+            println!("You can't bet more than you have!");
+            current_bet.0.pop();
+        }
+        else if current_bet.sum() > limits.maximum {
+            println!("The table maximum is {}!", limits.maximum);
+            current_bet.0.pop();
+        }
+        else if current_bet.sum() == balance && limits.validate(balance, balance).is_ok() {
+            break;
+        }
+    }
+
+    current_bet
+}
+
+/// Prints the basic-strategy play, the chance of busting on one more card and the value of each legal action,
+/// along with any Illustrious 18 or Fab 4 index play the live Hi-Lo count calls for.
+fn show_hint(engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) {
+    let seat_hand: &PlayerHand = &engine.hands()[seat][hand];
+    let up_card: u32 = match engine.dealer().up_card() {
+        Ok(card) => card.value(),
+        Err(_) => return,
+    };
+    let table: StrategyTable = StrategyTable::new(engine.rules());
+
+    if let Some(values) = table.values(&seat_hand.hand, up_card) {
+        println!("\x1b[1;33mHint:\x1b[0m {} ({} against {})", values.best(actions), seat_hand.hand.value(), up_card);
+        if let Some(probability) = seat_bust_probability(engine, seat, hand) {
+            println!("Chance to bust on the next card: {:.1}%", probability * 100.0);
+        }
+        // The exact values for the cards left in this shoe, next to the infinite-deck ones.
+        let exact: Option<ActionValues> = analyze_seat(engine, seat, hand);
+        // Both columns value a split without resplitting, which undersells it at tables that allow more than one.
+        let resplits: bool = engine.rules().max_splits > 1 && actions.contains(&Action::Split);
+        println!("  Action\tBasic\tThis shoe");
+        for action in actions {
+            if let Some(ev) = values.value(*action) {
+                let marker: &str = if resplits && *action == Action::Split { "~" } else { "" };
+                match exact.as_ref().and_then(|exact: &ActionValues| exact.value(*action)) {
+                    Some(exact) => println!("  {}\t{}{:+.3}\t{}{:+.3}", action, marker, ev, marker, exact),
+                    None => println!("  {}\t{}{:+.3}", action, marker, ev),
+                }
+            }
+        }
+        if resplits {
+            println!("  ~ approximate: one split, no resplits");
+        }
+        if let Some(exact) = exact.filter(|exact: &ActionValues| exact.best(actions) != values.best(actions)) {
+            println!("\x1b[1;33mThis shoe favors:\x1b[0m {}", exact.best(actions));
+        }
+
+        let true_count: f64 = hi_lo_true_count(engine);
+        println!("True count (Hi-Lo): {:+.1}", true_count);
+        let indices: IndexTable = IndexTable::standard();
+        let basic: Action = table.decide(seat_hand, up_card, actions);
+        if let Some(play) = indices.deviation(&seat_hand.hand, up_card, true_count, actions, basic) {
+            if play.action != values.best(actions) {
+                println!("\x1b[1;33mIndex play:\x1b[0m {} at {}", play.action, play.trigger);
+            }
+        }
+    }
+}
+
+fn prompt_play(engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) -> Action {
+    let mut options: Vec<&str> = [
+        (Action::Hit, "Hit: 'H'"),
+        (Action::Double, "Double-Down: 'D'"),
+        (Action::Stand, "Stand: 'S'"),
+        (Action::Split, "Split: 'P'"),
+        (Action::Surrender, "Surrender: 'R'"),
+    ]
+    .iter()
+    .filter(|(action, _)| actions.contains(action))
+    .map(|(_, option)| *option)
+    .collect();
+    options.push("Hint: '?'");
+
+    loop {
+        println!("\x1b[1;34m{}\x1b[0m", options.join(", "));
+
+        let action: Action = match read_response().as_str() {
+            "H" | "h" => Action::Hit,
+            "D" | "d" => Action::Double,
+            "S" | "s" => Action::Stand,
+            "P" | "p" => Action::Split,
+            "R" | "r" => Action::Surrender,
+            "?" => {
+                show_hint(engine, seat, hand, actions);
+                continue;
+            },
+            _ => {
+                println!("Invalid response! Please try again.");
+                continue;
+            },
+        };
+
+        if actions.contains(&action) {
+            return action;
+        }
+
+        match action {
+            Action::Double => println!("You can't double down on this hand."),
+            Action::Split => println!("You can't split this hand."),
+            _ => println!("You can't surrender this hand."),
+        }
+    }
+}
+
+impl PlayerController for StdinController {
+    fn name(&self) -> String {
+        String::from("Human")
+    }
+
+    fn decide_bet(&mut self, engine: &BlackjackEngine, seat: usize, balance: u32) -> u32 {
+        println!("\x1b[1;34mPlayer {}:\x1b[0m\t\x1b[31mBalance: {}\x1b[0m\tPlace your bet:", seat + 1, balance);
+
+        let suggestion: Option<u32> = match self.suggestion.as_mut() {
+            Some(betting) => {
+                let bet: u32 = engine.rules().limits.clamp(betting.next_bet(engine, seat), balance).unwrap_or(balance);
+                println!("\x1b[1;33mSuggested bet ({}):\x1b[0m {}", betting.name(), bet);
+                Some(bet)
+            },
+            None => None,
+        };
+        prompt_bet(balance, &engine.rules().limits, suggestion).sum()
+    }
+
+    fn decide_action(&mut self, engine: &BlackjackEngine, seat: usize, hand: usize, actions: &[Action]) -> Action {
+        if actions.contains(&Action::Decline) {
+            println!("\x1b[1;34mPlayer {}:\x1b[0m\tSurrender before the dealer peeks? 'Y', 'N'", seat + 1);
+            return match read_response().as_str() {
+                "Y" | "y" => Action::Surrender,
+                _ => Action::Decline,
+            };
+        }
+
+        prompt_play(engine, seat, hand, actions)
+    }
+
+    fn decide_insurance(&mut self, engine: &BlackjackEngine, seat: usize, max: u32) -> u32 {
+        let true_count: f64 = hi_lo_true_count(engine);
+        if IndexTable::standard().take_insurance(true_count) {
+            println!("\x1b[1;33mHint:\x1b[0m the true count is {:+.1}, insurance pays at +3 or more.", true_count);
+        }
+
+        if engine.hands()[seat][0].is_natural() {
+            println!("\x1b[1;34mPlayer {}:\x1b[0m\tEven money? 'Y', 'N'", seat + 1);
+            return match read_response().as_str() {
+                "Y" | "y" => max.max(1),
+                _ => 0,
+            };
+        }
+
+        println!("\x1b[1;34mPlayer {}:\x1b[0m\tInsurance? Enter an amount up to {} or 'N'", seat + 1, max);
+        loop {
+            match read_response().as_str() {
+                "N" | "n" | "" | "0" => return 0,
+                amount => match amount.parse::<u32>() {
+                    Ok(stake) if stake <= max => return stake,
+                    _ => println!("Invalid insurance! Enter an amount up to {} or 'N'", max),
+                },
+            }
+        }
+    }
+
+    fn is_human(&self) -> bool {
+        true
+    }
+}
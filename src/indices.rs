@@ -0,0 +1,199 @@
+use crate::{Hand, HandValue};
+use crate::chart::Play;
+use crate::engine::Action;
+use crate::strategy::HandKind;
+
+/// When an index play takes over from basic strategy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    AtOrAbove(f64),
+    Below(f64),
+}
+
+impl Trigger {
+    pub fn fires(&self, true_count: f64) -> bool {
+        match self {
+            Self::AtOrAbove(index) => true_count >= *index,
+            Self::Below(index) => true_count < *index,
+        }
+    }
+}
+
+impl std::fmt::Display for Trigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AtOrAbove(index) => write!(f, "TC >= {:+}", index),
+            Self::Below(index) => write!(f, "TC < {:+}", index),
+        }
+    }
+}
+
+/// Plays `action` instead of basic strategy for `kind` against `up_card` once the true count triggers it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IndexPlay {
+    pub kind: HandKind,
+    pub up_card: u32,
+    pub trigger: Trigger,
+    pub action: Action,
+}
+
+impl IndexPlay {
+    pub fn new(kind: HandKind, up_card: u32, trigger: Trigger, action: Action) -> Self {
+        Self { kind, up_card, trigger, action }
+    }
+
+    pub fn applies(&self, hand: &Hand, up_card: u32, true_count: f64, actions: &[Action]) -> bool {
+        if self.up_card != up_card || !self.trigger.fires(true_count) || !actions.contains(&self.action) {
+            return false;
+        }
+
+        let value: HandValue = hand.value();
+        match self.kind {
+            HandKind::Pair(pair) => hand.can_split() && hand[0].value() == pair,
+            HandKind::Soft(total) => value.soft && value.total == total,
+            HandKind::Hard(total) => !value.soft && value.total == total,
+        }
+    }
+}
+
+/// Count-based deviations from basic strategy, keyed on the Hi-Lo true count. Earlier plays take precedence.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IndexTable {
+    pub plays: Vec<IndexPlay>,
+    /// Insurance (and even money) is taken once this trigger fires.
+    pub insurance: Option<Trigger>,
+}
+
+impl IndexTable {
+    pub fn illustrious_18() -> Self {
+        use HandKind::{Hard, Pair};
+        use Trigger::{AtOrAbove, Below};
+
+        Self {
+            plays: vec![
+                IndexPlay::new(Hard(16), 10, AtOrAbove(0.0), Action::Stand),
+                IndexPlay::new(Hard(15), 10, AtOrAbove(4.0), Action::Stand),
+                IndexPlay::new(Pair(10), 5, AtOrAbove(5.0), Action::Split),
+                IndexPlay::new(Pair(10), 6, AtOrAbove(4.0), Action::Split),
+                IndexPlay::new(Hard(10), 10, AtOrAbove(4.0), Action::Double),
+                IndexPlay::new(Hard(12), 3, AtOrAbove(2.0), Action::Stand),
+                IndexPlay::new(Hard(12), 2, AtOrAbove(3.0), Action::Stand),
+                IndexPlay::new(Hard(11), 11, AtOrAbove(1.0), Action::Double),
+                IndexPlay::new(Hard(9), 2, AtOrAbove(1.0), Action::Double),
+                IndexPlay::new(Hard(10), 11, AtOrAbove(4.0), Action::Double),
+                IndexPlay::new(Hard(9), 7, AtOrAbove(3.0), Action::Double),
+                IndexPlay::new(Hard(16), 9, AtOrAbove(5.0), Action::Stand),
+                IndexPlay::new(Hard(13), 2, Below(-1.0), Action::Hit),
+                IndexPlay::new(Hard(12), 4, Below(0.0), Action::Hit),
+                IndexPlay::new(Hard(12), 5, Below(-2.0), Action::Hit),
+                IndexPlay::new(Hard(12), 6, Below(-1.0), Action::Hit),
+                IndexPlay::new(Hard(13), 3, Below(-2.0), Action::Hit),
+            ],
+            insurance: Some(AtOrAbove(3.0)),
+        }
+    }
+
+    pub fn fab_4() -> Self {
+        use HandKind::Hard;
+        use Trigger::AtOrAbove;
+
+        Self {
+            plays: vec![
+                IndexPlay::new(Hard(14), 10, AtOrAbove(3.0), Action::Surrender),
+                IndexPlay::new(Hard(15), 10, AtOrAbove(0.0), Action::Surrender),
+                IndexPlay::new(Hard(15), 9, AtOrAbove(2.0), Action::Surrender),
+                IndexPlay::new(Hard(15), 11, AtOrAbove(1.0), Action::Surrender),
+            ],
+            insurance: None,
+        }
+    }
+
+    /// The Fab 4 surrenders ahead of the Illustrious 18, so 15 vs 10 surrenders before it stands.
+    pub fn standard() -> Self {
+        Self::fab_4().with(Self::illustrious_18())
+    }
+
+    /// Appends `other`'s plays after these, keeping this table's insurance index if it has one.
+    pub fn with(mut self, other: IndexTable) -> Self {
+        self.plays.extend(other.plays);
+        self.insurance = self.insurance.or(other.insurance);
+        self
+    }
+
+    /// Reads one index per line: `<row> <up-card> <trigger> <play>`, e.g. `H16 10 >=0 S` or `H13 2 <-1 H`, using the
+    /// row labels and play codes of `Chart::parse`. `insurance >=3` sets the insurance index; `#` starts a comment.
+    pub fn parse(source: &str) -> std::io::Result<Self> {
+        let source: &str = source.strip_prefix('\u{FEFF}').unwrap_or(source);
+        let mut table: IndexTable = IndexTable::default();
+        let invalid = |number: usize, message: String| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("line {}: {}", number + 1, message))
+        };
+
+        for (number, line) in source.lines().enumerate() {
+            let line: &str = line.split('#').next().unwrap_or("").trim();
+            let words: Vec<&str> = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|word: &&str| !word.is_empty())
+                .collect();
+
+            match words.as_slice() {
+                [] => continue,
+                [label, trigger] if label.eq_ignore_ascii_case("insurance") => {
+                    let trigger: Trigger = parse_trigger(trigger).ok_or_else(|| invalid(number, format!("invalid trigger '{}'", trigger)))?;
+                    table.insurance = Some(trigger);
+                },
+                [label, up_card, trigger, play] => {
+                    let kind: HandKind = crate::chart::parse_label(label).ok_or_else(|| invalid(number, format!("unknown row '{}'", label)))?;
+                    let up_card: u32 = match up_card.to_uppercase().as_str() {
+                        "A" => 11,
+                        value => value.parse::<u32>().ok().filter(|value: &u32| (2..=10).contains(value))
+                            .ok_or_else(|| invalid(number, format!("invalid up-card '{}'", up_card)))?,
+                    };
+                    let trigger: Trigger = parse_trigger(trigger).ok_or_else(|| invalid(number, format!("invalid trigger '{}'", trigger)))?;
+                    let action: Action = match Play::parse(play) {
+                        Some(Play::Hit) => Action::Hit,
+                        Some(Play::Stand) => Action::Stand,
+                        Some(Play::Double) => Action::Double,
+                        Some(Play::Split) => Action::Split,
+                        Some(Play::SurrenderOrHit) => Action::Surrender,
+                        _ => return Err(invalid(number, format!("unknown play '{}'", play))),
+                    };
+                    table.plays.push(IndexPlay::new(kind, up_card, trigger, action));
+                },
+                _ => return Err(invalid(number, format!("expected '<row> <up-card> <trigger> <play>', found '{}'", line))),
+            }
+        }
+
+        Ok(table)
+    }
+
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// The first index play that fires for this hand, if any.
+    pub fn lookup(&self, hand: &Hand, up_card: u32, true_count: f64, actions: &[Action]) -> Option<&IndexPlay> {
+        self.plays.iter().find(|play: &&IndexPlay| play.applies(hand, up_card, true_count, actions))
+    }
+
+    /// The first play that may replace `basic`. Only a pair row overrides a split, so a pair of eights is never
+    /// played as hard 16, and only another surrender overrides a surrender.
+    pub fn deviation(&self, hand: &Hand, up_card: u32, true_count: f64, actions: &[Action], basic: Action) -> Option<&IndexPlay> {
+        self.plays.iter().find(|play: &&IndexPlay| {
+            play.applies(hand, up_card, true_count, actions)
+                && (basic != Action::Split || matches!(play.kind, HandKind::Pair(_)))
+                && (basic != Action::Surrender || play.action == Action::Surrender)
+        })
+    }
+
+    pub fn take_insurance(&self, true_count: f64) -> bool {
+        self.insurance.is_some_and(|trigger: Trigger| trigger.fires(true_count))
+    }
+}
+
+fn parse_trigger(trigger: &str) -> Option<Trigger> {
+    if let Some(index) = trigger.strip_prefix(">=") {
+        return index.parse::<f64>().ok().map(Trigger::AtOrAbove);
+    }
+    trigger.strip_prefix('<').and_then(|index: &str| index.parse::<f64>().ok()).map(Trigger::Below)
+}
@@ -1,4 +1,21 @@
 pub mod prelude;
+pub mod rules;
+pub mod payout;
+pub mod dealer;
+pub mod shoe;
+pub mod engine;
+pub mod controller;
+pub mod strategy;
+pub mod chart;
+pub mod counting;
+pub mod indices;
+pub mod simulation;
+pub mod composition;
+pub mod analyzer;
+pub mod house_edge;
+pub mod betting;
+pub mod bankroll;
+pub mod training;
 #[cfg(test)]
 pub mod test;
 
@@ -7,14 +24,29 @@ use rand::prelude::*;
 #[derive(Debug)]
 pub enum DeckError {
     EmptyDeck,
-    ErrorWhileBetting
+    ErrorWhileBetting,
+    InvalidSplit,
+    IllegalAction,
+    InsufficientBalance,
+    BelowTableMinimum(u32),
+    AboveTableMaximum(u32),
+    InvalidBetIncrement(u32),
 }
 
 impl std::error::Error for DeckError {}
 
 impl std::fmt::Display for DeckError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
+        match self {
+            Self::EmptyDeck => write!(f, "the deck is empty"),
+            Self::ErrorWhileBetting => write!(f, "an error occurred while betting"),
+            Self::InvalidSplit => write!(f, "this hand can't be split"),
+            Self::IllegalAction => write!(f, "this action isn't allowed right now"),
+            Self::InsufficientBalance => write!(f, "the balance can't cover this bet"),
+            Self::BelowTableMinimum(minimum) => write!(f, "the table minimum is {}", minimum),
+            Self::AboveTableMaximum(maximum) => write!(f, "the table maximum is {}", maximum),
+            Self::InvalidBetIncrement(increment) => write!(f, "bets must be a multiple of {}", increment),
+        }
     }
 }
 
@@ -64,7 +96,7 @@ impl Hand {
     }
 
     pub fn compare_to(&self, other_hand: &Hand) -> Result<std::cmp::Ordering, DeckError> {
-        match self.sum().cmp(&other_hand.sum()) {
+        match self.value().total.cmp(&other_hand.value().total) {
             std::cmp::Ordering::Equal => Ok(std::cmp::Ordering::Equal),
             std::cmp::Ordering::Greater => Ok(std::cmp::Ordering::Greater),
             std::cmp::Ordering::Less => Ok(std::cmp::Ordering::Less),
@@ -81,16 +113,16 @@ impl Hand {
         Ok(card)
     }
 
+    pub fn value(&self) -> HandValue {
+        HandValue::of(&self.0)
+    }
+
     pub fn sum(&self) -> u32 {
-        let mut total: u32 = 0;
-        for card in &self.0 {
-            total += card.rank.get_value();
-        }
-        total
+        self.value().total
     }
 
     pub fn is_blackjack(&self) -> bool {
-        self.sum() == 21 && self.0.len() == 2
+        self.value().blackjack
     }
 
     pub fn is_bust(&self, sum: u32) -> bool {
@@ -106,24 +138,80 @@ impl Hand {
         false
     }
 
-    pub fn level_off_ace(&self) -> u32 {
-        if self.is_bust(self.sum()) && self.contains(Rank::Ace) {
-            return self.sum() - 10;
+    pub fn can_split(&self) -> bool {
+        self.0.len() == 2 && self.0[0].value() == self.0[1].value()
+    }
+
+    pub fn split(&mut self) -> Result<Hand, DeckError> {
+        if !self.can_split() {
+            return Err(DeckError::InvalidSplit);
+        }
+        match self.0.pop() {
+            Some(card) => Ok(Hand(vec![card], false)),
+            None => Err(DeckError::InvalidSplit),
         }
-        self.sum()
     }
 
-    pub fn check(&self, player_index: u32, mut player_hands: Vec<Hand>) -> bool {
-        if self.is_blackjack() {
+    pub fn level_off_ace(&self) -> u32 {
+        self.value().total
+    }
+
+    pub fn check(&self, player_index: u32) -> bool {
+        let value: HandValue = self.value();
+        if value.blackjack {
             return true;
         }
-        let total: u32 = self.level_off_ace();
-        if self.is_bust(total) {
+        if value.bust {
             println!("\x1b[1;31mplayer {} busted!\x1b[0m", player_index + 1);
-            player_hands[player_index as usize] = Hand(Vec::new(), true);
             return true;
         }
-        return false;
+        false
+    }
+}
+
+/// The best total of a set of cards: every Ace counts as 11 until that would bust the hand, then as 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandValue {
+    pub total: u32,
+    pub soft: bool,
+    pub blackjack: bool,
+    pub bust: bool,
+}
+
+impl HandValue {
+    pub fn of(cards: &[Card]) -> Self {
+        let mut total: u32 = 0;
+        let mut soft_aces: u32 = 0;
+
+        for card in cards {
+            total += card.value();
+            if card.rank == Rank::Ace {
+                soft_aces += 1;
+            }
+        }
+
+        while total > 21 && soft_aces > 0 {
+            total -= 10;
+            soft_aces -= 1;
+        }
+
+        Self {
+            total,
+            soft: soft_aces > 0,
+            blackjack: total == 21 && cards.len() == 2,
+            bust: total > 21,
+        }
+    }
+}
+
+impl std::fmt::Display for HandValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.blackjack, self.bust, self.soft) {
+            (true, _, _) => write!(f, "Blackjack"),
+            (_, true, _) => write!(f, "{} (bust)", self.total),
+            (_, _, true) => write!(f, "soft {}", self.total),
+            _ => write!(f, "{}", self.total),
+        }
     }
 }
 
@@ -136,6 +224,42 @@ impl std::fmt::Display for Hand {
     }
 }
 
+#[derive(Clone)]
+pub struct PlayerHand {
+    pub hand: Hand,
+    pub bet: Bet,
+    pub split: bool,
+    pub doubled: bool,
+    pub insurance: u32,
+    pub even_money: bool,
+    pub surrendered: bool,
+}
+
+impl PlayerHand {
+    pub fn new(hand: Hand, bet: Bet) -> Self {
+        Self { hand, bet, split: false, doubled: false, insurance: 0, even_money: false, surrendered: false }
+    }
+
+    /// Hands created by a split never count as a natural, even with two cards worth 21.
+    pub fn is_natural(&self) -> bool {
+        !self.split && self.hand.is_blackjack()
+    }
+
+    pub fn is_split_aces(&self) -> bool {
+        self.split && self.hand.0.first().is_some_and(|card: &Card| card.rank == Rank::Ace)
+    }
+
+    pub fn can_surrender(&self) -> bool {
+        !self.split && self.hand.0.len() == 2
+    }
+
+    pub fn double_down(&mut self) {
+        let stake: Vec<Chip> = self.bet.0.clone();
+        self.bet.0.extend(stake);
+        self.doubled = true;
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Deck(Vec<Card>);
 
@@ -155,12 +279,20 @@ impl Deck {
     }
 
     pub fn shuffle(&mut self) {
+        self.shuffle_with(&mut rand::thread_rng());
+    }
+
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         for i in (0..self.0.len()).rev() {
-            let j = rand::thread_rng().gen_range(0..i + 1);
+            let j = rng.gen_range(0..i + 1);
             self.0.swap(i, j);
         }
     }
 
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle_with(&mut StdRng::seed_from_u64(seed));
+    }
+
     pub fn reshuffle(&mut self, multiplier: u32) {
         let deck: Deck = Deck::build(multiplier);
         *self = deck;
@@ -175,6 +307,10 @@ impl Deck {
         &self.0
     }
 
+    pub fn draw(&mut self) -> Result<Card, DeckError> {
+        self.0.pop().ok_or(DeckError::EmptyDeck)
+    }
+
     pub fn deal_hand(&mut self, cards: u32) -> Result<Hand, DeckError> {
         let mut hand: Vec<Card> = Vec::<Card>::new();
 
@@ -213,7 +349,7 @@ impl Card {
 
     pub fn reveal(&mut self) -> Card {
         self.hidden = false;
-        return *self;
+        *self
     }
 
     pub fn is_hidden(&self) -> bool {
@@ -223,6 +359,14 @@ impl Card {
     pub fn value(&self) -> u32 {
         self.rank.get_value()
     }
+
+    pub fn rank(&self) -> Rank {
+        self.rank
+    }
+
+    pub fn suit(&self) -> Suit {
+        self.suit
+    }
 }
 
 impl std::fmt::Display for Card {
@@ -245,10 +389,10 @@ pub enum Suit {
 impl std::fmt::Display for Suit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Diamonds => write!(f, "{}", "\x1b[31m♦\x1b[0m"),
-            Self::Hearts => write!(f, "{}", "\x1b[31m♥\x1b[0m"),
-            Self::Clubs => write!(f, "{}", "\x1b[36m♣\x1b[0m"),
-            Self::Spades => write!(f, "{}", "\x1b[36m♠\x1b[0m"),
+            Self::Diamonds => write!(f, "\x1b[31m♦\x1b[0m"),
+            Self::Hearts => write!(f, "\x1b[31m♥\x1b[0m"),
+            Self::Clubs => write!(f, "\x1b[36m♣\x1b[0m"),
+            Self::Spades => write!(f, "\x1b[36m♠\x1b[0m"),
         }
     }
 }
@@ -339,7 +483,7 @@ impl IntoChips for u32 {
         let mut value: u32 = self;
         let mut chips: Vec<Chip> = Vec::new();
         
-        for chip in Chip::all_chips() {
+        for chip in Chip::all_chips().into_iter().rev() {
             while value >= chip.value() {
                 chips.push(chip);
                 value -= chip.value();
@@ -2,19 +2,27 @@ use std::io;
 use deckbuilder::prelude::*;
 
 pub mod util;
+pub mod human;
+pub mod trainer;
+pub mod simulate;
 use crate::util::*;
+use crate::human::*;
+use crate::trainer::*;
+use crate::simulate::*;
 
 pub struct Game {
-    players: Table,
-    player_amount: u32,
-    deck: Deck,
-    round: u64,
-    end_game: bool
+    engine: BlackjackEngine,
+    controllers: Vec<Box<dyn PlayerController>>,
+    end_game: bool,
+    history: HandHistory,
 }
 
 impl Game {
-    fn init_game_from(loadout: Loadout) -> Self {
+    fn init_game_from(loadout: Loadout, seed: Option<u64>) -> Self {
+        let seed: u64 = seed.unwrap_or_else(rand::random);
+
         println!("\x1b[1;34m### CLI BLACKJACK ###\x1b[0m");
+        println!("\x1b[1;34mSeed:\x1b[0m {}", seed);
         println!("Enter the amount of players:");
 
         let mut response: String = String::new();
@@ -24,236 +32,177 @@ impl Game {
 
         let player_count: u32 = response.trim().parse::<u32>().expect("Invalid player count");
 
+        let rules: TableRules = Self::select_rules();
+        println!("\x1b[1;34mTable:\x1b[0m {}", rules);
+
         let mut p: Vec<Player> = Vec::new();
+        let mut controllers: Vec<Box<dyn PlayerController>> = Vec::new();
         for i in 0..player_count {
             p.push(Player(i, Chip::from_loadout(loadout.clone()), Bet(vec![]), true));
+            controllers.push(Self::select_controller(i, seed, &rules));
         }
 
+        let shoe: Shoe = Shoe::from_rules_with_seed(&rules, seed);
+
         Self { 
-            players: Table(p), 
-            player_amount: player_count,
-            deck: Deck::build(2),
-            round: 1,
-            end_game: false
+            engine: BlackjackEngine::new(rules, shoe, Table(p)),
+            controllers,
+            end_game: false,
+            history: HandHistory::new(seed),
         }
     }
 
-    fn init_euro5_game() -> Self {
-        Self::init_game_from(Loadout::Euro5)
-    }
-
-    fn start_game(&mut self) -> Result<(), DeckError>{
-        while !self.end_game {
-            let game_over: Option<bool> = self.betting_phase();
-            if game_over.is_none() {
-                return Err(DeckError::ErrorWhileBetting);
-            }
+    fn select_rules() -> TableRules {
+        let presets: Vec<TableRules> = TableRules::presets();
 
-            if game_over.unwrap() {
-                println!("\x1b[1;31mAll players have gone bankrupt!\x1b[0m");
-                println!();
-                println!("\x1b[1;34mHands played:\x1b[0m \t{}", self.round - 1);  
-                println!();
-    
-                for (index, player) in self.players.0.iter().enumerate() {
-                    show_final_results(index, player);
-                }
-                
-                break;
-            }
-
-            self.game_round()?;
+        println!("Select the table rules (default: 1):");
+        for (index, preset) in presets.iter().enumerate() {
+            println!("'{}': {}", index + 1, preset.name);
         }
 
-        Ok(())
-    }
+        loop {
+            let mut response: String = String::new();
+            io::stdin()
+                .read_line(&mut response)
+                .expect("Failed to read line.");
 
-    fn betting_phase(&mut self) -> Option<bool> {
+            if response.trim().is_empty() {
+                return TableRules::default();
+            }
 
-        if game_over(&self.players.0) {
-            return Some(true);
+            match response.trim().parse::<usize>() {
+                Ok(choice) if choice >= 1 && choice <= presets.len() => return presets[choice - 1].clone(),
+                _ => println!("Invalid table! Please try again."),
+            }
         }
+    }
 
-        for player in &mut self.players.0 {
-            player.2 = Bet(vec![]);
+    fn select_betting() -> Option<Box<dyn BettingStrategy>> {
+        let names: Vec<String> = betting_strategies(10).iter().map(|betting| format!("'{}'", betting.name())).collect();
+        println!("{}", names.join(", "));
+
+        loop {
+            match read_response().as_str() {
+                "" => return None,
+                name => match betting_strategy_by_name(name, 10) {
+                    Some(betting) => return Some(betting),
+                    None => println!("Unknown betting strategy! Please try again."),
+                },
+            }
         }
+    }
 
-        println!();
-        println!("\x1b[1;34m### Betting Phase ###\x1b[0m");
-        
-        for player in 0..self.player_amount {
-
-            let player_instance: &mut Player = &mut self.players.0[player as usize];
-
-            if !player_instance.is_active() {
-                continue;
+    fn select_controller(player: u32, seed: u64, rules: &TableRules) -> Box<dyn PlayerController> {
+        println!("\x1b[1;34mPlayer {}:\x1b[0m Human: 'H' (default), Basic Strategy Bot: 'B', Random Bot: 'R', Scripted: 'F', Chart Bot: 'C', Index Bot: 'I'", player + 1);
+
+        loop {
+            match read_response().as_str() {
+                "" | "H" | "h" => {
+                    println!("Betting strategy to suggest bets (empty for none):");
+                    return Box::new(StdinController::new(Self::select_betting()));
+                },
+                "B" | "b" => {
+                    println!("Betting strategy (empty for flat bets):");
+                    let bot: BasicStrategyBot = BasicStrategyBot::new(10);
+                    return Box::new(match Self::select_betting() {
+                        Some(betting) => bot.with_betting(betting),
+                        None => bot,
+                    });
+                },
+                "R" | "r" => return Box::new(RandomBot::new(25, seed.wrapping_add(player as u64))),
+                "F" | "f" => {
+                    println!("Path to the script:");
+                    match ScriptedController::from_file(&read_response()) {
+                        Ok(controller) => return Box::new(controller),
+                        Err(e) => println!("Couldn't load the script: {}", e),
+                    }
+                },
+                "I" | "i" => {
+                    println!("Path to an index table (empty for the Illustrious 18 and Fab 4):");
+                    let indices: std::io::Result<IndexTable> = match read_response().as_str() {
+                        "" => Ok(IndexTable::standard()),
+                        path => IndexTable::from_file(path),
+                    };
+                    match indices {
+                        Ok(indices) => return Box::new(BasicStrategyBot::new(10).with_indices(indices)),
+                        Err(e) => println!("Couldn't load the index table: {}", e),
+                    }
+                },
+                "C" | "c" => {
+                    println!("Path to the chart:");
+                    match Chart::from_file(&read_response()) {
+                        Ok(chart) => {
+                            show_deviations(&chart.deviations(&StrategyTable::new(rules)));
+                            return Box::new(ChartBot::new(10, chart));
+                        },
+                        Err(e) => println!("Couldn't load the chart: {}", e),
+                    }
+                },
+                _ => println!("Invalid player type! Please try again."),
             }
+        }
+    }
 
-            println!("\x1b[1;34mPlayer {}:\x1b[0m\t\x1b[31mBalance: {}\x1b[0m\tPlace your bet:", player + 1, 
-                self.players.get_player_by_id(player)?.get_balance());
-            
-            let mut current_bet: Bet = Bet(vec![]);
+    fn init_euro5_game(seed: Option<u64>) -> Self {
+        Self::init_game_from(Loadout::Euro5, seed)
+    }
 
-            loop {
-                println!("'1', '5', '10', '25', '100', '500', '1000', 'All-In', 'Ok'");
-                println!("Current bet: \x1b[1;32m{}\x1b[0m", current_bet.sum());
-    
-                let mut response: String = String::new();
-    
-                io::stdin()
-                    .read_line(&mut response)
-                    .expect("Failed to read line");
-
-                match response.as_str().trim() {
-                    "1" => current_bet.0.push(Chip::C1),
-                    "5" => current_bet.0.push(Chip::C5),
-                    "10" => current_bet.0.push(Chip::C10),
-                    "25" => current_bet.0.push(Chip::C25),
-                    "100" => current_bet.0.push(Chip::C100),
-                    "500" => current_bet.0.push(Chip::C500),
-                    "1000" => current_bet.0.push(Chip::C1000),
-                    "All-In" | "A" | "a" => {
-                        let player_balance: u32 = self.players.get_player_by_id(player)?.get_balance();
-                        current_bet.0 = player_balance.into_chips();
-                        break;
-                    },
-                    "Ok" | "ok" | "o" => {
-                        if current_bet.sum() == 0 {
-                            println!("You must place a bet!");
-                            continue;
-                        } else {
-                            break;
-                        }
-                    },
-                    _ => {
-                        println!("You must place a bet!");
-                        continue;
-                    },
-                }
-
-                if current_bet.sum() > self.players.get_player_by_id(player)?.get_balance() {
-                    println!("You can't bet more than you have!");
-                    current_bet.0 = self.players.get_player_by_id(player)?.get_balance().into_chips();
-                } 
-                else if current_bet.sum() == self.players.get_player_by_id(player)?.get_balance() {
-                    break;
-                }
+    fn start_game(&mut self) -> Result<(), DeckError>{
+        while !self.end_game {
+            if self.engine.is_game_over() {
+                println!("\x1b[1;31mAll players have gone bankrupt!\x1b[0m");
+                self.show_final_results();
+                break;
             }
 
-            self.players.0[player as usize].2 = current_bet;
-        }
-
-        println!();
-        println!("\x1b[1;34m### Betting Phase is Over! ###\x1b[0m");
-        println!("All bet's were placed!");
-        
-        for player in 0..self.player_amount {
-            println!("\x1b[1;34mPlayer {}:\x1b[0m\tBet: \x1b[31m{}\x1b[0m", player + 1, self.players.0[player as usize].2.sum());
+            self.game_round()?;
         }
 
-        Some(false)
+        Ok(())
     }
 
-    fn game_round(&mut self) -> Result<(), DeckError> {
+    fn show_final_results(&self) {
+        println!();
+        println!("\x1b[1;34m### Final Results ###\x1b[0m");
         println!();
-        println!("\x1b[1;34m### Round {}! ###\x1b[0m", self.round);
-        print!("\n");
-
-        self.deck.reshuffle(2);
-        let mut player_hands: Vec<Hand> = Vec::new();
-
-        let mut dealer_hand: Hand = Hand(vec![], false);
-        dealer_hand.draw_from(&mut self.deck)?;
-        dealer_hand.draw_from_hidden(&mut self.deck)?;
 
-        show_dealer_hand(&dealer_hand);
+        println!("\x1b[1;34mHands played:\x1b[0m \t{}", self.engine.round());  
+        println!();
 
-        for _ in 0..self.player_amount as usize {
-            player_hands.push(self.deck.deal_hand(2)?);
+        for (index, player) in self.engine.players().0.iter().enumerate() {
+            show_final_results(index, player);
         }
 
-        show_player_hands(&self.players, &player_hands, 0);
+        println!();
+        print!("{}", self.history);
+    }
+
+    fn game_round(&mut self) -> Result<(), DeckError> {
+        let events: Vec<Event> = self.engine.start_round()?;
+        self.render(&events);
 
-        for player in 0..self.player_amount as usize {
+        while let Some(decision) = self.engine.decision() {
+            let seat: usize = decision.seat();
+            let action: Action = self.controllers[seat].decide(&self.engine, &decision);
 
-            if !self.players.0[player].is_active() {
-                continue;
+            if !self.controllers[seat].is_human() {
+                println!("\x1b[1;34mPlayer {} ({}):\x1b[0m\t{}", seat + 1, self.controllers[seat].name(), action);
             }
 
-            let mut moves: u32 = 0;
-            let mut player_stands: bool = false;
-            let mut player_busted: bool = false;
-            let mut hand: Hand = player_hands[player].clone();
-
-            while !player_stands {
-                moves += 1;
-                println!("\x1b[1;34mHit: 'H', Double-Down: 'D', Stand: 'S'\x1b[0m");
-
-                let mut response: String = String::new();
-
-                io::stdin()
-                    .read_line(&mut response)
-                    .expect("Failed to read line");
-
-                match response.as_str().trim() {
-                    "H" | "h" => {
-                        hand.draw_from(&mut self.deck)?;
-                        player_stands = hand.check(player as u32, player_hands.clone());
-                        player_busted = player_stands;
-                    },
-                    "D" | "d" => {
-                        if !(moves > 1) {
-                            hand.draw_from(&mut self.deck)?;
-                            player_busted = hand.check(player as u32, player_hands.clone());
-                            player_stands = true;
-                        } else {
-                            println!("You can't double down on this hand.");
-                        }
-                    },
-                    "S" | "s" => player_stands = true,
-                    _ => println!("Invalid response! Please try again."),
-                }
-
-                match player_busted {
-                    false => player_hands[player] = hand.clone(),
-                    true => player_hands[player] = Hand(hand.0.clone(), true),
-                }
-
-                match !player_stands {
-                    true => show_player_hands(&self.players, &player_hands, player as u32),
-                    false => show_player_hands(&self.players, &player_hands, player as u32 + 1),
-                };
+            match self.engine.apply(action) {
+                Ok(events) => self.render(&events),
+                // Only a person gets another try; a bot would make the same choice again.
+                Err(e) if !self.controllers[seat].is_human() => return Err(e),
+                Err(DeckError::IllegalAction) => println!("You can't do that right now."),
+                Err(DeckError::InsufficientBalance) => println!("You can't bet more than you have!"),
+                Err(e @ (DeckError::BelowTableMinimum(_) | DeckError::AboveTableMaximum(_) | DeckError::InvalidBetIncrement(_))) => {
+                    println!("Invalid bet: {}.", e)
+                },
+                Err(e) => return Err(e),
             }
         }
 
-        let local: Hand = dealer_hand.clone();
-        dealer_hand[local.last_index() as usize] = dealer_hand.last()?.reveal();
-        show_dealer_hand(&dealer_hand);
-
-        println!("\x1b[1;31mDealer plays...\x1b[0m");
-        
-        let mut new_hand: Hand = Hand(vec![], false);
-        match dealer_logic(&mut self.deck, &mut dealer_hand, false) {
-            Ok(final_hand) => {
-                println!("\x1b[1;31mDealer's final hand:\x1b[0m\t{}", final_hand);
-                new_hand = final_hand.clone();
-                if final_hand.busted() {
-                    println!("\x1b[1;31mDealer busted!\x1b[0m");
-                } else {
-                    println!("\x1b[1;31mDealer stands with a total of {}\x1b[0m", final_hand.sum());
-                }
-            },
-            Err(e) => println!("Error during dealer play: {:?}", e), 
-        }
-
-        dealer_hand = new_hand;
-
-        println!("\x1b[1;34m### Results of Round {} ###\x1b[0m", self.round);
-        println!();
-
-        show_results(&mut self.players, &player_hands, &dealer_hand)?;
-
-        self.round += 1;
+        self.history.record(self.engine.round(), self.engine.dealer(), self.engine.hands(), self.engine.settlements());
         
         let mut response: String = String::new();
     
@@ -265,33 +214,170 @@ impl Game {
     
         if response.trim() == "Q" || response.trim() == "q" {
             self.end_game = true;
+            self.show_final_results();
+        }
 
-            println!();
-            println!("\x1b[1;34m### Final Results ###\x1b[0m");
-            println!();
+        Ok(())
+    }
 
-            println!("\x1b[1;34mHands played:\x1b[0m \t{}", self.round - 1);  
-            println!();
+    fn render(&self, events: &[Event]) {
+        let mut hands_changed: bool = false;
 
-            for (index, player) in self.players.0.iter().enumerate() {
-                show_final_results(index, player);
+        for event in events {
+            if hands_changed && matches!(event, Event::DealerPlayed) {
+                show_player_hands(self.engine.hands(), None);
+                hands_changed = false;
             }
-        }
 
-        for (index, player) in self.players.0.clone().iter().enumerate() {
-            if player.1.sum() <= 0 && player.is_active() {
-                println!("\x1b[1;31mPlayer {} has gone bankrupt!\x1b[0m", index + 1);
-                self.players.0[index].bankrupt();
+            match event {
+                Event::Shuffled => println!("\x1b[1;31mThe cut card is out! Shuffling the shoe...\x1b[0m"),
+                Event::DiscardsShuffled => println!("\x1b[1;31mThe shoe ran out! Shuffling the discards back in...\x1b[0m"),
+                Event::RoundStarted { .. } => {
+                    println!();
+                    println!("\x1b[1;34m### Betting Phase ###\x1b[0m");
+                },
+                Event::Dealt => {
+                    println!();
+                    println!("\x1b[1;34m### Betting Phase is Over! ###\x1b[0m");
+                    println!("All bet's were placed!");
+
+                    for (index, player) in self.engine.players().0.iter().enumerate() {
+                        if player.is_active() {
+                            println!("\x1b[1;34mPlayer {}:\x1b[0m\tBet: \x1b[31m{}\x1b[0m", index + 1, player.2.sum());
+                        }
+                    }
+
+                    println!();
+                    println!("\x1b[1;34m### Round {}! ###\x1b[0m (seed: {})", self.engine.round(), self.history.seed);
+                    println!();
+                    println!("Shoe: {}", self.engine.shoe());
+
+                    show_dealer_hand(self.engine.dealer());
+                    hands_changed = true;
+                },
+                Event::EarlySurrendered { seat } => println!("\x1b[1;34mPlayer {}:\x1b[0m\tSurrenders early.", seat + 1),
+                Event::InsuranceTaken { seat, amount } => println!("\x1b[1;34mPlayer {}:\x1b[0m\tInsurance: {}", seat + 1, amount),
+                Event::EvenMoneyTaken { seat } => println!("\x1b[1;34mPlayer {}:\x1b[0m\tTakes even money.", seat + 1),
+                Event::DealerPeeked { blackjack: true } => println!("\x1b[1;31mDealer peeks... Blackjack!\x1b[0m"),
+                Event::DealerPeeked { blackjack: false } => println!("\x1b[1;31mDealer peeks... no Blackjack.\x1b[0m"),
+                Event::Busted { seat, .. } => println!("\x1b[1;31mplayer {} busted!\x1b[0m", seat + 1),
+                Event::CardDrawn { .. } | Event::Doubled { .. } | Event::Split { .. } | Event::Stood { .. } | Event::Surrendered { .. } => {
+                    hands_changed = true;
+                },
+                Event::DealerPlayed => {
+                    let dealer: &Dealer = self.engine.dealer();
+                    println!("\x1b[1;31mDealer plays...\x1b[0m");
+                    show_dealer_hand(dealer);
+
+                    if dealer.has_blackjack() {
+                        println!("\x1b[1;31mDealer has Blackjack!\x1b[0m");
+                    } else if dealer.hand().busted() {
+                        println!("\x1b[1;31mDealer busted!\x1b[0m");
+                    } else {
+                        println!("\x1b[1;31mDealer stands with a total of {}\x1b[0m", dealer.hand().sum());
+                    }
+
+                    println!("\x1b[1;34m### Results of Round {} ###\x1b[0m", self.engine.round());
+                    println!();
+                    show_results(self.engine.settlements());
+                },
+                Event::Bankrupt { seat } => println!("\x1b[1;31mPlayer {} has gone bankrupt!\x1b[0m", seat + 1),
+                Event::SatOut { seat } => println!("\x1b[1;34mPlayer {}:\x1b[0m\tSits out, the balance is below the table minimum.", seat + 1),
+                Event::BetPlaced { .. } | Event::Settled { .. } | Event::RoundOver { .. } => {},
             }
         }
 
-        player_hands.clear();
-        Ok(())
+        if hands_changed {
+            let active: Option<(usize, usize)> = match self.engine.phase() {
+                Phase::Playing { seat, hand } => Some((seat, hand)),
+                _ => None,
+            };
+            show_player_hands(self.engine.hands(), active);
+        }
+    }
+}
+
+fn parse_seed(args: &[String]) -> Result<Option<u64>, String> {
+    match args.iter().position(|arg: &String| arg == "--seed") {
+        None => Ok(None),
+        Some(index) => match args.get(index + 1).map(|value: &String| value.parse::<u64>()) {
+            Some(Ok(seed)) => Ok(Some(seed)),
+            _ => Err(String::from("'--seed' expects an unsigned integer")),
+        },
     }
 }
 
+/// Looks up `--rules <preset>` by its number in `TableRules::presets` or by name.
+fn parse_rules(args: &[String]) -> Result<TableRules, String> {
+    let name: &String = match args.iter().position(|arg: &String| arg == "--rules") {
+        None => return Ok(TableRules::default()),
+        Some(index) => args.get(index + 1).ok_or_else(|| String::from("'--rules' expects a table preset"))?,
+    };
+    let presets: Vec<TableRules> = TableRules::presets();
+
+    match name.parse::<usize>() {
+        Ok(choice) if choice >= 1 && choice <= presets.len() => Ok(presets[choice - 1].clone()),
+        _ => presets
+            .into_iter()
+            .find(|preset: &TableRules| preset.name.to_lowercase().replace(' ', "-") == name.to_lowercase())
+            .ok_or_else(|| format!("unknown table preset '{}'", name)),
+    }
+}
+
+fn option_value<'a>(args: &'a [String], option: &str) -> Option<&'a String> {
+    args.iter().position(|arg: &String| arg == option).and_then(|index: usize| args.get(index + 1))
+}
+
+fn parse_option<T: std::str::FromStr>(args: &[String], option: &str, default: T) -> Result<T, String> {
+    match option_value(args, option) {
+        None => Ok(default),
+        Some(value) => value.parse::<T>().map_err(|_| format!("invalid value '{}' for '{}'", value, option)),
+    }
+}
+
+/// `chart [--rules <preset>] [--import <file>] [--csv]` prints the basic strategy chart for a table,
+/// or an imported chart together with its deviations from basic strategy.
+fn run_chart(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let rules: TableRules = parse_rules(args)?;
+    let table: StrategyTable = StrategyTable::new(&rules);
+
+    let chart: Chart = match option_value(args, "--import") {
+        Some(path) => Chart::from_file(path)?,
+        None => Chart::from_table(&table),
+    };
+
+    if args.iter().any(|arg: &String| arg == "--csv") {
+        print!("{}", chart);
+        return Ok(());
+    }
+
+    println!("\x1b[1;34mTable:\x1b[0m {}", rules);
+    show_chart(&chart);
+
+    if option_value(args, "--import").is_some() {
+        println!();
+        show_deviations(&chart.deviations(&table));
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut game: Game = Game::init_euro5_game();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(|command: &String| command.as_str()) {
+        Some("chart") => return run_chart(&args[1..]),
+        Some("train") if args.get(1).is_some_and(|mode: &String| mode == "drill") => return run_drill(&args[2..]),
+        Some("train") => return run_trainer(&args[1..]),
+        Some("simulate") => return run_simulation(&args[1..]),
+        Some("edge") => return run_house_edge(&args[1..]),
+        Some("bankroll") => return run_bankroll(&args[1..]),
+        _ => {},
+    }
+
+    let seed: Option<u64> = parse_seed(&args)?;
+
+    let mut game: Game = Game::init_euro5_game(seed);
     game.start_game()?;
 
     Ok(())
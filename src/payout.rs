@@ -0,0 +1,160 @@
+use std::cmp::Ordering;
+use crate::{Balance, Hand, IntoChips, PlayerHand};
+use crate::rules::{HoleCard, TableRules};
+
+/// How a payout that doesn't come out to a whole chip (e.g. 3:2 on an odd bet) is paid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChipRounding {
+    Down,
+    Nearest,
+    Up,
+}
+
+impl ChipRounding {
+    pub fn apply(&self, numerator: u32, denominator: u32) -> u32 {
+        match self {
+            Self::Down => numerator / denominator,
+            Self::Nearest => (numerator + denominator / 2) / denominator,
+            Self::Up => numerator.div_ceil(denominator),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Blackjack,
+    Win,
+    Push,
+    Loss,
+    Bust,
+    EvenMoney,
+    Insurance,
+    Surrender,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blackjack => write!(f, "Blackjack!"),
+            Self::Win => write!(f, "Win!"),
+            Self::Push => write!(f, "Push!"),
+            Self::Loss => write!(f, "Loss!"),
+            Self::Bust => write!(f, "Busted!"),
+            Self::EvenMoney => write!(f, "Even Money!"),
+            Self::Insurance => write!(f, "Insurance"),
+            Self::Surrender => write!(f, "Surrendered"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settlement {
+    pub outcome: Outcome,
+    pub wager: u32,
+    /// Chips won (positive) or lost (negative) by the hand.
+    pub net: i64,
+}
+
+impl Settlement {
+    pub fn new(outcome: Outcome, wager: u32, net: i64) -> Self {
+        Self { outcome, wager, net }
+    }
+
+    pub fn apply(&self, balance: &mut Balance) {
+        let total: i64 = balance.sum() as i64 + self.net;
+        balance.0 = (total.max(0) as u32).into_chips();
+    }
+}
+
+pub fn committed_bets(seat_hands: &[PlayerHand]) -> u32 {
+    seat_hands.iter().map(|seat_hand: &PlayerHand| seat_hand.bet.sum() + seat_hand.insurance).sum()
+}
+
+pub fn max_insurance(seat_hand: &PlayerHand) -> u32 {
+    seat_hand.bet.sum() / 2
+}
+
+/// Insurance pays 2:1 if the dealer holds a natural and is lost otherwise.
+pub fn settle_insurance(stake: u32, dealer_hand: &Hand) -> Settlement {
+    match dealer_hand.is_blackjack() {
+        true => Settlement::new(Outcome::Insurance, stake, 2 * stake as i64),
+        false => Settlement::new(Outcome::Insurance, stake, -(stake as i64)),
+    }
+}
+
+pub fn natural_winnings(wager: u32, rules: &TableRules) -> u32 {
+    let (numerator, denominator) = rules.blackjack_payout.ratio();
+    rules.chip_rounding.apply(wager * numerator, denominator)
+}
+
+pub fn settle(seat_hand: &PlayerHand, dealer_hand: &Hand, rules: &TableRules) -> Settlement {
+    let wager: u32 = seat_hand.bet.sum();
+    let dealer_natural: bool = dealer_hand.is_blackjack();
+
+    if seat_hand.even_money {
+        return Settlement::new(Outcome::EvenMoney, wager, wager as i64);
+    }
+
+    // Without a peek, a late surrender is only settled once the dealer's natural is known, and that takes the whole wager.
+    if seat_hand.surrendered {
+        let refund: u32 = match dealer_natural && rules.hole_card == HoleCard::NoHoleCard {
+            true => 0,
+            false => rules.chip_rounding.apply(wager, 2),
+        };
+        return Settlement::new(Outcome::Surrender, wager, refund as i64 - wager as i64);
+    }
+
+    if seat_hand.is_natural() {
+        return match dealer_natural {
+            true => Settlement::new(Outcome::Push, wager, 0),
+            false => Settlement::new(Outcome::Blackjack, wager, natural_winnings(wager, rules) as i64),
+        };
+    }
+
+    if dealer_natural {
+        let lost: u32 = match rules.original_bets_only && seat_hand.doubled {
+            true => wager / 2,
+            false => wager,
+        };
+        return Settlement::new(Outcome::Loss, wager, -(lost as i64));
+    }
+
+    if seat_hand.hand.busted() {
+        return Settlement::new(Outcome::Bust, wager, -(wager as i64));
+    }
+
+    if dealer_hand.busted() {
+        return Settlement::new(Outcome::Win, wager, wager as i64);
+    }
+
+    match dealer_hand.compare_to(&seat_hand.hand) {
+        Ok(Ordering::Less) => Settlement::new(Outcome::Win, wager, wager as i64),
+        Ok(Ordering::Greater) => Settlement::new(Outcome::Loss, wager, -(wager as i64)),
+        _ => Settlement::new(Outcome::Push, wager, 0),
+    }
+}
+
+pub fn settle_round(player_hands: &[Vec<PlayerHand>], dealer_hand: &Hand, rules: &TableRules) -> Vec<Vec<Settlement>> {
+    player_hands
+        .iter()
+        .map(|seat_hands: &Vec<PlayerHand>| {
+            let mut settlements: Vec<Settlement> = seat_hands
+                .iter()
+                .enumerate()
+                .map(|(hand_index, seat_hand): (usize, &PlayerHand)| {
+                    // Only the first hand of a split seat carries the original bet.
+                    match hand_index > 0 && rules.original_bets_only && dealer_hand.is_blackjack() {
+                        true => Settlement::new(Outcome::Push, seat_hand.bet.sum(), 0),
+                        false => settle(seat_hand, dealer_hand, rules),
+                    }
+                })
+                .collect();
+
+            for seat_hand in seat_hands.iter().filter(|seat_hand: &&PlayerHand| seat_hand.insurance > 0) {
+                settlements.push(settle_insurance(seat_hand.insurance, dealer_hand));
+            }
+
+            settlements
+        })
+        .collect()
+}
@@ -1,2 +1,19 @@
 
-pub use crate::{Deck, Card, Suit, Rank, Hand, DeckError, Bet, Chip, Loadout, Balance, IntoChips};
\ No newline at end of file
+pub use crate::{Deck, Card, Suit, Rank, Hand, HandValue, PlayerHand, DeckError, Bet, Chip, Loadout, Balance, IntoChips};
+pub use crate::rules::{TableRules, TableLimits, BelowMinimum, BlackjackPayout, Surrender, HoleCard};
+pub use crate::payout::{Outcome, Settlement, ChipRounding, settle, settle_round, committed_bets, natural_winnings, max_insurance, settle_insurance};
+pub use crate::dealer::{Dealer, HoleCardState, dealer_logic};
+pub use crate::shoe::Shoe;
+pub use crate::engine::{BlackjackEngine, Action, Decision, Event, Phase, Player, Table, game_over};
+pub use crate::controller::{PlayerController, BasicStrategyBot, RandomBot, ScriptedController, ChartBot, hi_lo_true_count};
+pub use crate::strategy::{StrategyTable, ActionValues, HandKind, DealerOutcomes, dealer_outcomes};
+pub use crate::chart::{Chart, Play, Deviation, row_label};
+pub use crate::counting::{CountingSystem, Counter, TagTable, HiLo, KO, HiOptI, HiOptII, OmegaII, Zen, WongHalves, systems, system_by_name};
+pub use crate::indices::{IndexTable, IndexPlay, Trigger};
+pub use crate::simulation::{SimulationConfig, SimulationStats, simulate, simulate_parallel, derive_seed};
+pub use crate::composition::{Composition, dealer_probabilities};
+pub use crate::analyzer::{analyze, analyze_seat, bust_probability, seat_bust_probability};
+pub use crate::house_edge::{RuleContribution, house_edge, deck_count_effect, rule_contributions};
+pub use crate::betting::{BettingStrategy, FlatBet, Martingale, Paroli, OneThreeTwoSix, DAlembert, BetRamp, Kelly, betting_strategies, betting_strategy_by_name};
+pub use crate::bankroll::{RiskProfile, BankrollReport};
+pub use crate::training::{Score, Checkpoints, running_count_correct, true_count_correct};
@@ -0,0 +1,259 @@
+use crate::DeckError;
+use crate::payout::ChipRounding;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlackjackPayout {
+    ThreeToTwo,
+    SixToFive,
+    EvenMoney,
+}
+
+impl BlackjackPayout {
+    /// Returns the payout ratio as `(numerator, denominator)`.
+    pub fn ratio(&self) -> (u32, u32) {
+        match self {
+            Self::ThreeToTwo => (3, 2),
+            Self::SixToFive => (6, 5),
+            Self::EvenMoney => (1, 1),
+        }
+    }
+}
+
+impl std::fmt::Display for BlackjackPayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (numerator, denominator) = self.ratio();
+        write!(f, "{}:{}", numerator, denominator)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Surrender {
+    None,
+    Late,
+    Early,
+}
+
+impl std::fmt::Display for Surrender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Late => write!(f, "late"),
+            Self::Early => write!(f, "early"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HoleCard {
+    /// The dealer takes a hole card and peeks for blackjack (American style).
+    Peek,
+    /// The dealer only takes a second card after all players have acted (European style).
+    NoHoleCard,
+}
+
+impl std::fmt::Display for HoleCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Peek => write!(f, "peek"),
+            Self::NoHoleCard => write!(f, "no hole card"),
+        }
+    }
+}
+
+/// Limits on the bet that opens each round. The minimum should itself be a multiple of the increment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableLimits {
+    pub minimum: u32,
+    pub maximum: u32,
+    pub increment: u32,
+}
+
+impl TableLimits {
+    pub fn new(minimum: u32, maximum: u32, increment: u32) -> Self {
+        Self { minimum, maximum, increment }
+    }
+
+    /// Checks a bet against the balance first, then the limits, so the error names the first thing to fix.
+    pub fn validate(&self, amount: u32, balance: u32) -> Result<(), DeckError> {
+        if amount > balance {
+            return Err(DeckError::InsufficientBalance);
+        }
+        if amount == 0 || amount < self.minimum {
+            return Err(DeckError::BelowTableMinimum(self.minimum));
+        }
+        if amount > self.maximum {
+            return Err(DeckError::AboveTableMaximum(self.maximum));
+        }
+        if !amount.is_multiple_of(self.increment.max(1)) {
+            return Err(DeckError::InvalidBetIncrement(self.increment));
+        }
+        Ok(())
+    }
+
+    /// The legal bet nearest to `amount` that `balance` covers, rounded down to the increment,
+    /// or `None` if the balance can't cover the minimum.
+    pub fn clamp(&self, amount: u32, balance: u32) -> Option<u32> {
+        let increment: u32 = self.increment.max(1);
+        let most: u32 = self.maximum.min(balance) / increment * increment;
+        let least: u32 = self.minimum.max(1);
+
+        match most < least {
+            true => None,
+            false => Some((amount.clamp(least, most) / increment * increment).max(least)),
+        }
+    }
+}
+
+/// No minimum beyond a single chip and no maximum.
+impl Default for TableLimits {
+    fn default() -> Self {
+        Self { minimum: 1, maximum: u32::MAX, increment: 1 }
+    }
+}
+
+impl std::fmt::Display for TableLimits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.maximum, self.increment) {
+            (u32::MAX, 1) => write!(f, "{} and up", self.minimum),
+            (u32::MAX, increment) => write!(f, "{} and up in steps of {}", self.minimum, increment),
+            (maximum, 1) => write!(f, "{}-{}", self.minimum, maximum),
+            (maximum, increment) => write!(f, "{}-{} in steps of {}", self.minimum, maximum, increment),
+        }
+    }
+}
+
+/// What happens to a seat whose balance falls below the table minimum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BelowMinimum {
+    /// The seat leaves the table as if it had gone broke.
+    Bankrupt,
+    /// The seat keeps its place but sits out rounds until a rebuy covers the minimum. An empty balance is still bankrupt.
+    SitOut,
+}
+
+impl std::fmt::Display for BelowMinimum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bankrupt => write!(f, "bankrupt"),
+            Self::SitOut => write!(f, "sit out"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableRules {
+    pub name: String,
+    pub decks: u32,
+    pub hit_soft_17: bool,
+    pub blackjack_payout: BlackjackPayout,
+    pub chip_rounding: ChipRounding,
+    pub double_after_split: bool,
+    /// How many times a seat may split; `3` allows up to four hands.
+    pub max_splits: u32,
+    pub surrender: Surrender,
+    pub insurance: bool,
+    pub hole_card: HoleCard,
+    /// Without a hole card, a dealer blackjack only takes the original bets and returns doubles and splits.
+    pub original_bets_only: bool,
+    /// Fraction of the shoe dealt before the cut card is reached.
+    pub penetration: f64,
+    pub limits: TableLimits,
+    pub below_minimum: BelowMinimum,
+}
+
+impl TableRules {
+    pub fn vegas_strip() -> Self {
+        Self {
+            name: String::from("Vegas Strip"),
+            decks: 4,
+            hit_soft_17: false,
+            blackjack_payout: BlackjackPayout::ThreeToTwo,
+            chip_rounding: ChipRounding::Down,
+            double_after_split: true,
+            max_splits: 3,
+            surrender: Surrender::None,
+            insurance: true,
+            hole_card: HoleCard::Peek,
+            original_bets_only: true,
+            penetration: 0.75,
+            limits: TableLimits::new(10, 5000, 5),
+            below_minimum: BelowMinimum::Bankrupt,
+        }
+    }
+
+    pub fn atlantic_city() -> Self {
+        Self {
+            name: String::from("Atlantic City"),
+            decks: 8,
+            surrender: Surrender::Late,
+            penetration: 0.8,
+            limits: TableLimits::new(10, 2000, 5),
+            ..Self::vegas_strip()
+        }
+    }
+
+    pub fn european() -> Self {
+        Self {
+            name: String::from("European"),
+            decks: 6,
+            max_splits: 1,
+            hole_card: HoleCard::NoHoleCard,
+            original_bets_only: false,
+            penetration: 0.7,
+            limits: TableLimits::new(5, 1000, 5),
+            ..Self::vegas_strip()
+        }
+    }
+
+    pub fn downtown() -> Self {
+        Self {
+            name: String::from("Downtown"),
+            decks: 2,
+            hit_soft_17: true,
+            penetration: 0.65,
+            limits: TableLimits::new(5, 500, 1),
+            ..Self::vegas_strip()
+        }
+    }
+
+    pub fn presets() -> Vec<TableRules> {
+        vec![
+            Self::vegas_strip(),
+            Self::atlantic_city(),
+            Self::european(),
+            Self::downtown(),
+        ]
+    }
+}
+
+impl Default for TableRules {
+    fn default() -> Self {
+        Self::vegas_strip()
+    }
+}
+
+impl std::fmt::Display for TableRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let hole_card: String = match self.hole_card {
+            HoleCard::Peek => self.hole_card.to_string(),
+            HoleCard::NoHoleCard if self.original_bets_only => format!("{} (OBO)", self.hole_card),
+            HoleCard::NoHoleCard => format!("{} (all bets lost)", self.hole_card),
+        };
+
+        write!(
+            f,
+            "{}: {} deck(s), {}, blackjack pays {}, {}, max splits: {}, surrender: {}, insurance: {}, {}, penetration: {:.0}%, limits: {}",
+            self.name,
+            self.decks,
+            if self.hit_soft_17 { "H17" } else { "S17" },
+            self.blackjack_payout,
+            if self.double_after_split { "DAS" } else { "no DAS" },
+            self.max_splits,
+            self.surrender,
+            if self.insurance { "yes" } else { "no" },
+            hole_card,
+            self.penetration * 100.0,
+            self.limits
+        )
+    }
+}
@@ -0,0 +1,141 @@
+use rand::prelude::*;
+use crate::{Card, Deck, DeckError, Hand};
+use crate::rules::TableRules;
+
+/// A dealing shoe that keeps its cards between rounds and is only reshuffled once the cut card comes out.
+#[derive(Clone, Debug)]
+pub struct Shoe {
+    deck: Deck,
+    decks: u32,
+    penetration: f64,
+    cut_card: u32,
+    discards: Vec<Card>,
+    burned: Option<Card>,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Shoe {
+    pub fn new(decks: u32, penetration: f64) -> Self {
+        Self::with_seed(decks, penetration, rand::thread_rng().gen())
+    }
+
+    /// Every shuffle of a seeded shoe is drawn from the same RNG, so the whole shoe sequence is reproducible.
+    pub fn with_seed(decks: u32, penetration: f64, seed: u64) -> Self {
+        let mut shoe: Shoe = Self {
+            deck: Deck::build(decks),
+            decks,
+            penetration: penetration.clamp(0.0, 1.0),
+            cut_card: 0,
+            discards: Vec::new(),
+            burned: None,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        };
+        shoe.shuffle();
+        shoe
+    }
+
+    pub fn from_rules(rules: &TableRules) -> Self {
+        Self::new(rules.decks, rules.penetration)
+    }
+
+    pub fn from_rules_with_seed(rules: &TableRules, seed: u64) -> Self {
+        Self::with_seed(rules.decks, rules.penetration, seed)
+    }
+
+    /// Collects every card back into the shoe, shuffles, places the cut card and burns the top card.
+    pub fn shuffle(&mut self) {
+        self.deck = Deck::build(self.decks);
+        self.deck.shuffle_with(&mut self.rng);
+        self.discards.clear();
+
+        let total: f64 = self.deck.total_cards() as f64;
+        self.cut_card = (total * (1.0 - self.penetration)).round() as u32;
+
+        self.burned = self.deck.0.pop().map(|mut card: Card| card.reveal());
+        if let Some(card) = self.burned {
+            self.discards.push(card);
+        }
+    }
+
+    /// Shuffles the discard tray back in under the cards still in the shoe, for a round that runs the shoe dry.
+    /// Cards on the table stay out until they are discarded. Returns `false` if the tray is empty.
+    pub fn reshuffle_discards(&mut self) -> bool {
+        if self.discards.is_empty() {
+            return false;
+        }
+
+        let mut cards: Vec<Card> = std::mem::take(&mut self.discards);
+        cards.shuffle(&mut self.rng);
+        cards.append(&mut self.deck.0);
+        self.deck.0 = cards;
+        self.burned = None;
+        true
+    }
+
+    pub fn deck_mut(&mut self) -> &mut Deck {
+        &mut self.deck
+    }
+
+    pub fn deck(&self) -> &Deck {
+        &self.deck
+    }
+
+    pub fn draw(&mut self) -> Result<Card, DeckError> {
+        self.deck.0.pop().ok_or(DeckError::EmptyDeck)
+    }
+
+    pub fn cut_card_reached(&self) -> bool {
+        self.deck.total_cards() <= self.cut_card
+    }
+
+    pub fn discard(&mut self, hand: &Hand) {
+        for card in &hand.0 {
+            let mut card: Card = *card;
+            self.discards.push(card.reveal());
+        }
+    }
+
+    pub fn discard_tray(&self) -> &[Card] {
+        &self.discards
+    }
+
+    /// The discard tray without the burn card, which goes in face down.
+    pub fn discards_seen(&self) -> &[Card] {
+        match self.burned {
+            Some(_) => &self.discards[1..],
+            None => &self.discards,
+        }
+    }
+
+    pub fn burned(&self) -> Option<Card> {
+        self.burned
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.deck.total_cards()
+    }
+
+    pub fn remaining_decks(&self) -> f64 {
+        self.remaining() as f64 / 52.0
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn decks(&self) -> u32 {
+        self.decks
+    }
+
+    pub fn penetration(&self) -> f64 {
+        self.penetration
+    }
+}
+
+impl std::fmt::Display for Shoe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} card(s) left, {} in the discard tray", self.remaining(), self.discards.len())
+    }
+}
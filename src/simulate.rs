@@ -0,0 +1,171 @@
+use std::time::Instant;
+use deckbuilder::prelude::*;
+use crate::{option_value, parse_option, parse_rules};
+
+/// `simulate [--rounds <n>] [--rules <preset>] [--decks <n>] [--penetration <0-1>] [--seats <n>] [--unit <n>]
+/// [--ramp <1,2,4,8>] [--betting <strategy>] [--indices] [--seed <n>] [--threads <n>]` plays bots through the engine without
+/// any prompts and prints the results. The thread count defaults to the available cores; the same seed and thread count
+/// repeat a run exactly.
+pub fn run_simulation(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let config: SimulationConfig = simulation_config(args)?;
+    let threads: usize = thread_count(args)?;
+
+    println!("\x1b[1;34m### Simulation ###\x1b[0m");
+    println!("\x1b[1;34mTable:\x1b[0m {}", config.rules);
+    println!(
+        "\x1b[1;34mSeats:\x1b[0m {}\t\x1b[1;34mUnit:\x1b[0m {}\t\x1b[1;34mBetting:\x1b[0m {}\t\x1b[1;34mIndices:\x1b[0m {}\t\x1b[1;34mSeed:\x1b[0m {}",
+        config.seats,
+        config.ramp.unit,
+        config.betting.clone().unwrap_or_else(|| format!("ramp {:?}", config.ramp.spread)),
+        if config.indices { "yes" } else { "no" },
+        config.seed
+    );
+    println!("\x1b[1;34mThreads:\x1b[0m {}", threads);
+
+    let started: Instant = Instant::now();
+    let stats: SimulationStats = simulate_parallel(&config, threads)?;
+
+    println!();
+    println!("{}", stats);
+    println!();
+    println!("Finished in {:.1}s", started.elapsed().as_secs_f64());
+
+    Ok(())
+}
+
+/// `edge [--rules <preset>] [--decks <n>] [--rounds <n>] [--seed <n>] [--threads <n>]` prints the estimated house edge of
+/// basic strategy broken down by rule and deck count, then checks it against a simulation of the actual shoe, which also
+/// plays resplits (`--rounds 0` skips it).
+pub fn run_house_edge(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut rules: TableRules = parse_rules(args)?;
+    rules.decks = deck_count(args, rules.decks)?;
+
+    println!("\x1b[1;34m### House Edge ###\x1b[0m");
+    println!("\x1b[1;34mTable:\x1b[0m {}", rules);
+    println!();
+
+    let contributions: Vec<RuleContribution> = rule_contributions(&rules);
+    for contribution in &contributions {
+        println!("{:<48}{:+.3}%", contribution.rule, contribution.edge * 100.0);
+    }
+    let estimated: f64 = contributions.iter().map(|contribution: &RuleContribution| contribution.edge).sum();
+    println!("\x1b[1;34m{:<48}{:+.3}%\x1b[0m", "Estimated", estimated * 100.0);
+
+    let rounds: u64 = parse_option(args, "--rounds", 2_000_000)?;
+    if rounds == 0 {
+        return Ok(());
+    }
+
+    let config: SimulationConfig = SimulationConfig::new(rules, rounds, parse_option(args, "--seed", rand::random())?);
+    let stats: SimulationStats = simulate_parallel(&config, thread_count(args)?)?;
+
+    println!();
+    println!(
+        "\x1b[1;34mSimulated check:\x1b[0m {:+.3}% (± {:.3}%, {} rounds)",
+        stats.house_edge() * 100.0,
+        stats.house_edge_error() * 100.0,
+        stats.rounds
+    );
+
+    Ok(())
+}
+
+/// `bankroll [--bankroll <n>] [--rounds-per-hour <n>] [--risk <0-1>] [--flat]` followed by any `simulate` option prints the
+/// risk of ruin, N0, SCORE and hourly win for a game. The win rate comes from a simulation of the game, or with `--flat`
+/// from the infinite-deck house edge of flat betting.
+pub fn run_bankroll(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let config: SimulationConfig = simulation_config(args)?;
+
+    println!("\x1b[1;34m### Bankroll ###\x1b[0m");
+    println!("\x1b[1;34mTable:\x1b[0m {}", config.rules);
+
+    let profile: RiskProfile = match args.iter().any(|arg: &String| arg == "--flat") {
+        true => {
+            println!("\x1b[1;34mBetting:\x1b[0m flat {}", config.ramp.unit);
+            RiskProfile::flat(&config.rules, config.ramp.unit)
+        },
+        false => {
+            println!(
+                "\x1b[1;34mBetting:\x1b[0m {}\t\x1b[1;34mIndices:\x1b[0m {}\t\x1b[1;34mSimulated:\x1b[0m {} rounds",
+                config.betting.clone().unwrap_or_else(|| format!("ramp {:?}", config.ramp.spread)),
+                if config.indices { "yes" } else { "no" },
+                config.rounds
+            );
+            RiskProfile::from_stats(&simulate_parallel(&config, thread_count(args)?)?)
+        },
+    };
+
+    let report: BankrollReport = BankrollReport {
+        profile,
+        bankroll: parse_option(args, "--bankroll", 100 * config.ramp.unit)?,
+        rounds_per_hour: parse_option(args, "--rounds-per-hour", 100)?.max(1),
+        target_risk: parse_option(args, "--risk", 0.05)?,
+    };
+
+    println!();
+    println!("{}", report);
+
+    Ok(())
+}
+
+/// Reads the options shared by the commands that run a simulation.
+fn simulation_config(args: &[String]) -> Result<SimulationConfig, Box<dyn std::error::Error>> {
+    let mut rules: TableRules = parse_rules(args)?;
+    rules.decks = deck_count(args, rules.decks)?;
+    rules.penetration = parse_option(args, "--penetration", rules.penetration)?.clamp(0.1, 0.95);
+
+    // The engine shuffles the discards back in when a round outruns the shoe, so a single deck covers a full table.
+    let seats: usize = parse_option(args, "--seats", 1)?;
+    if !(1..=7).contains(&seats) {
+        return Err(format!("'--seats' must be between 1 and 7, not {}", seats).into());
+    }
+
+    let requested: u32 = parse_option(args, "--unit", rules.limits.minimum.max(10))?;
+    let unit: u32 = rules.limits.clamp(requested, u32::MAX).ok_or_else(|| format!("no legal bet at a {} table", rules.limits))?;
+    if unit != requested {
+        eprintln!("\x1b[1;33mWarning:\x1b[0m a unit of {} isn't a legal bet at this table ({}), betting {} instead.", requested, rules.limits, unit);
+    }
+    let ramp: BetRamp = match option_value(args, "--ramp") {
+        Some(spread) => BetRamp::parse(unit, spread).ok_or_else(|| format!("invalid bet ramp '{}'", spread))?,
+        None => BetRamp::flat(unit),
+    };
+    // Naming the count ramp with `--ramp` given just follows that ramp; any other strategy has no use for one.
+    let betting: Option<String> = match (option_value(args, "--betting"), option_value(args, "--ramp")) {
+        (Some(name), ramp) => {
+            let name: String = match betting_strategy_by_name(name, unit) {
+                Some(strategy) => strategy.name(),
+                None => {
+                    let names: Vec<String> = betting_strategies(unit).iter().map(|strategy| format!("'{}'", strategy.name())).collect();
+                    return Err(format!("unknown betting strategy '{}', expected one of {}", name, names.join(", ")).into());
+                },
+            };
+            match (ramp, name == BetRamp::flat(unit).name()) {
+                (None, _) => Some(name),
+                (Some(_), true) => None,
+                (Some(_), false) => return Err(format!("'--ramp' only applies to the count ramp, not {}", name).into()),
+            }
+        },
+        (None, _) => None,
+    };
+
+    Ok(SimulationConfig {
+        rounds: parse_option(args, "--rounds", 1_000_000)?,
+        seats,
+        ramp,
+        indices: args.iter().any(|arg: &String| arg == "--indices"),
+        betting,
+        ..SimulationConfig::new(rules, 0, parse_option(args, "--seed", rand::random())?)
+    })
+}
+
+fn deck_count(args: &[String], default: u32) -> Result<u32, String> {
+    match parse_option(args, "--decks", default)? {
+        0 => Err(String::from("'--decks' must be at least 1")),
+        decks => Ok(decks),
+    }
+}
+
+fn thread_count(args: &[String]) -> Result<usize, String> {
+    let cores: usize = std::thread::available_parallelism().map(|cores| cores.get()).unwrap_or(1);
+    Ok(parse_option(args, "--threads", cores)?.max(1))
+}
@@ -0,0 +1,265 @@
+use crate::{DeckError, PlayerHand};
+use crate::betting::{betting_strategy_by_name, BetRamp, BettingStrategy};
+use crate::controller::{BasicStrategyBot, PlayerController};
+use crate::engine::{BlackjackEngine, Player, Table};
+use crate::indices::IndexTable;
+use crate::payout::{Outcome, Settlement};
+use crate::rules::TableRules;
+use crate::shoe::Shoe;
+use crate::{Balance, Bet, IntoChips};
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub rules: TableRules,
+    pub rounds: u64,
+    pub seats: usize,
+    pub seed: u64,
+    pub ramp: BetRamp,
+    /// Play the Illustrious 18 and Fab 4 on top of basic strategy.
+    pub indices: bool,
+    /// A betting strategy for `betting_strategy_by_name`, betting `ramp.unit` as its unit instead of following the ramp.
+    pub betting: Option<String>,
+}
+
+impl SimulationConfig {
+    pub fn new(rules: TableRules, rounds: u64, seed: u64) -> Self {
+        Self { rules, rounds, seats: 1, seed, ramp: BetRamp::flat(10), indices: false, betting: None }
+    }
+}
+
+/// Totals over every seat and round. Everything is counted in whole chips so runs can be merged exactly.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SimulationStats {
+    pub rounds: u64,
+    /// One per seat per round.
+    pub seat_rounds: u64,
+    pub hands: u64,
+    pub initial_wagered: u64,
+    pub total_wagered: u64,
+    pub net: i64,
+    /// Sum of the squared net result of each seat-round, for the standard deviation.
+    pub net_squared: u128,
+    pub wins: u64,
+    pub losses: u64,
+    pub pushes: u64,
+    pub naturals: u64,
+    pub dealer_naturals: u64,
+    pub busts: u64,
+    pub dealer_busts: u64,
+    pub splits: u64,
+    pub doubles: u64,
+    pub surrenders: u64,
+    /// The flat unit the figures are reported in.
+    pub unit: u32,
+}
+
+impl SimulationStats {
+    pub fn merge(&mut self, other: &SimulationStats) {
+        self.rounds += other.rounds;
+        self.seat_rounds += other.seat_rounds;
+        self.hands += other.hands;
+        self.initial_wagered += other.initial_wagered;
+        self.total_wagered += other.total_wagered;
+        self.net += other.net;
+        self.net_squared += other.net_squared;
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.pushes += other.pushes;
+        self.naturals += other.naturals;
+        self.dealer_naturals += other.dealer_naturals;
+        self.busts += other.busts;
+        self.dealer_busts += other.dealer_busts;
+        self.splits += other.splits;
+        self.doubles += other.doubles;
+        self.surrenders += other.surrenders;
+        self.unit = self.unit.max(other.unit);
+    }
+
+    /// The player's loss as a fraction of the initial bets.
+    pub fn house_edge(&self) -> f64 {
+        match self.initial_wagered {
+            0 => 0.0,
+            wagered => -self.net as f64 / wagered as f64,
+        }
+    }
+
+    /// Mean result per seat-round, in units.
+    pub fn mean(&self) -> f64 {
+        match self.seat_rounds {
+            0 => 0.0,
+            rounds => self.net as f64 / rounds as f64 / self.unit.max(1) as f64,
+        }
+    }
+
+    /// Standard deviation of a seat-round, in units.
+    pub fn standard_deviation(&self) -> f64 {
+        if self.seat_rounds == 0 {
+            return 0.0;
+        }
+        let rounds: f64 = self.seat_rounds as f64;
+        let unit: f64 = self.unit.max(1) as f64;
+        let mean: f64 = self.net as f64 / rounds;
+        ((self.net_squared as f64 / rounds - mean * mean).max(0.0)).sqrt() / unit
+    }
+
+    /// Standard error of `house_edge`, taking each seat-round as one sample.
+    pub fn house_edge_error(&self) -> f64 {
+        match self.initial_wagered {
+            0 => 0.0,
+            wagered => self.standard_deviation() * self.unit.max(1) as f64 * (self.seat_rounds as f64).sqrt() / wagered as f64,
+        }
+    }
+
+    fn per_hand(&self, count: u64) -> f64 {
+        match self.hands {
+            0 => 0.0,
+            hands => count as f64 / hands as f64,
+        }
+    }
+
+    fn record(&mut self, engine: &BlackjackEngine) {
+        self.rounds += 1;
+        if engine.dealer().has_blackjack() {
+            self.dealer_naturals += 1;
+        }
+        if engine.dealer().hand().busted() {
+            self.dealer_busts += 1;
+        }
+
+        for (seat_hands, seat_settlements) in engine.hands().iter().zip(engine.settlements()) {
+            if seat_hands.is_empty() {
+                continue;
+            }
+
+            let net: i64 = seat_settlements.iter().map(|settlement: &Settlement| settlement.net).sum();
+            self.seat_rounds += 1;
+            self.net += net;
+            self.net_squared += (net.unsigned_abs() as u128).pow(2);
+            self.initial_wagered += seat_hands[0].bet.sum() as u64 / if seat_hands[0].doubled { 2 } else { 1 };
+            self.splits += seat_hands.len() as u64 - 1;
+            if seat_hands[0].is_natural() {
+                self.naturals += 1;
+            }
+
+            for (seat_hand, settlement) in seat_hands.iter().zip(seat_settlements) {
+                self.record_hand(seat_hand, settlement);
+            }
+        }
+    }
+
+    fn record_hand(&mut self, seat_hand: &PlayerHand, settlement: &Settlement) {
+        self.hands += 1;
+        self.total_wagered += seat_hand.bet.sum() as u64;
+        self.doubles += seat_hand.doubled as u64;
+        self.busts += seat_hand.hand.busted() as u64;
+        self.surrenders += (settlement.outcome == Outcome::Surrender) as u64;
+
+        match settlement.net.signum() {
+            1 => self.wins += 1,
+            -1 => self.losses += 1,
+            _ => self.pushes += 1,
+        }
+    }
+}
+
+impl std::fmt::Display for SimulationStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let percent = |count: u64| self.per_hand(count) * 100.0;
+
+        writeln!(f, "Rounds:\t\t\t{} ({} hands)", self.rounds, self.hands)?;
+        writeln!(f, "House edge:\t\t{:.3}%", self.house_edge() * 100.0)?;
+        writeln!(f, "Mean result:\t\t{:+.4} units per round", self.mean())?;
+        writeln!(f, "Standard deviation:\t{:.3} units per round", self.standard_deviation())?;
+        writeln!(f, "Average bet:\t\t{:.2} units", self.initial_wagered as f64 / self.seat_rounds.max(1) as f64 / self.unit.max(1) as f64)?;
+        writeln!(f, "Win / Loss / Push:\t{:.2}% / {:.2}% / {:.2}%", percent(self.wins), percent(self.losses), percent(self.pushes))?;
+        writeln!(f, "Naturals:\t\t{:.2}% (dealer {:.2}%)", percent(self.naturals), self.dealer_naturals as f64 / self.rounds.max(1) as f64 * 100.0)?;
+        writeln!(f, "Busts:\t\t\t{:.2}% (dealer {:.2}%)", percent(self.busts), self.dealer_busts as f64 / self.rounds.max(1) as f64 * 100.0)?;
+        writeln!(f, "Splits:\t\t\t{:.2}%", percent(self.splits))?;
+        writeln!(f, "Doubles:\t\t{:.2}%", percent(self.doubles))?;
+        write!(f, "Surrenders:\t\t{:.2}%", percent(self.surrenders))
+    }
+}
+
+/// Plays `config.rounds` rounds through the engine with strategy bots in every seat.
+/// Seats are topped back up between rounds so nobody goes bankrupt mid-run.
+pub fn simulate(config: &SimulationConfig) -> Result<SimulationStats, DeckError> {
+    let bankroll: u32 = config.ramp.max_bet().saturating_mul(1000).max(1000);
+    let players: Vec<Player> = (0..config.seats)
+        .map(|seat: usize| Player(seat as u32, Balance(bankroll.into_chips()), Bet(vec![]), true))
+        .collect();
+
+    let shoe: Shoe = Shoe::from_rules_with_seed(&config.rules, config.seed);
+    let mut engine: BlackjackEngine = BlackjackEngine::new(config.rules.clone(), shoe, Table(players));
+    let mut controllers: Vec<BasicStrategyBot> = (0..config.seats)
+        .map(|_| {
+            let betting: Box<dyn BettingStrategy> = match config.betting.as_deref().and_then(|name: &str| betting_strategy_by_name(name, config.ramp.unit)) {
+                Some(betting) => betting,
+                None => Box::new(config.ramp.clone()),
+            };
+            let bot: BasicStrategyBot = BasicStrategyBot::new(config.ramp.unit).with_betting(betting);
+            match config.indices {
+                true => bot.with_indices(IndexTable::standard()),
+                false => bot,
+            }
+        })
+        .collect();
+
+    let mut stats: SimulationStats = SimulationStats { unit: config.ramp.unit, ..SimulationStats::default() };
+
+    for _ in 0..config.rounds {
+        for seat in 0..config.seats {
+            if engine.players().0[seat].get_balance() < bankroll / 2 {
+                engine.rebuy(seat, bankroll)?;
+            }
+        }
+
+        engine.start_round()?;
+        while let Some(decision) = engine.decision() {
+            let action = controllers[decision.seat()].decide(&engine, &decision);
+            engine.apply(action)?;
+        }
+
+        stats.record(&engine);
+    }
+
+    Ok(stats)
+}
+
+/// SplitMix64 step, so neighbouring thread indices still get unrelated seeds.
+pub fn derive_seed(master: u64, index: u64) -> u64 {
+    let mut z: u64 = master.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Splits the rounds over `threads` independent tables, each seeded with `derive_seed(config.seed, index)`.
+/// The statistics are whole-chip sums, so the merged result is identical for the same seed and thread count.
+pub fn simulate_parallel(config: &SimulationConfig, threads: usize) -> Result<SimulationStats, DeckError> {
+    let threads: usize = threads.max(1);
+    let configs: Vec<SimulationConfig> = (0..threads)
+        .map(|index: usize| SimulationConfig {
+            rounds: config.rounds / threads as u64 + ((index as u64) < config.rounds % threads as u64) as u64,
+            seed: derive_seed(config.seed, index as u64),
+            ..config.clone()
+        })
+        .collect();
+
+    let results: Vec<Result<SimulationStats, DeckError>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = configs
+            .iter()
+            .map(|config: &SimulationConfig| scope.spawn(move || simulate(config)))
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("simulation thread panicked"))
+            .collect()
+    });
+
+    let mut stats: SimulationStats = SimulationStats { unit: config.ramp.unit, ..SimulationStats::default() };
+    for result in results {
+        stats.merge(&result?);
+    }
+    Ok(stats)
+}
@@ -0,0 +1,375 @@
+use std::collections::{BTreeMap, HashMap};
+use crate::{Hand, HandValue, PlayerHand};
+use crate::engine::Action;
+use crate::indices::IndexTable;
+use crate::rules::{HoleCard, Surrender, TableRules};
+
+/// Card values as counted by `Rank::get_value`, with the Ace as 11.
+pub const CARD_VALUES: [u32; 10] = [2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+/// Chance of drawing a card of `value` from an infinite shoe.
+pub fn card_probability(value: u32) -> f64 {
+    match value {
+        10 => 4.0 / 13.0,
+        _ => 1.0 / 13.0,
+    }
+}
+
+/// Adds a card to a `(total, soft)` hand state, demoting an Ace from 11 to 1 if the total would bust.
+pub fn add_card(total: u32, soft: bool, value: u32) -> (u32, bool) {
+    let mut total: u32 = total + value;
+    let mut soft_aces: u32 = soft as u32 + (value == 11) as u32;
+
+    while total > 21 && soft_aces > 0 {
+        total -= 10;
+        soft_aces -= 1;
+    }
+
+    (total, soft_aces > 0)
+}
+
+/// Probabilities of the dealer's final hand: 17 through 21, blackjack and bust.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DealerOutcomes(pub [f64; 7]);
+
+impl DealerOutcomes {
+    pub const BLACKJACK: usize = 5;
+    pub const BUST: usize = 6;
+
+    pub fn total(&self, total: u32) -> f64 {
+        match total {
+            17..=21 => self.0[total as usize - 17],
+            _ => 0.0,
+        }
+    }
+
+    pub fn blackjack(&self) -> f64 {
+        self.0[Self::BLACKJACK]
+    }
+
+    pub fn bust(&self) -> f64 {
+        self.0[Self::BUST]
+    }
+
+    pub(crate) fn add(&mut self, other: &DealerOutcomes, weight: f64) {
+        for (outcome, probability) in self.0.iter_mut().zip(other.0.iter()) {
+            *outcome += probability * weight;
+        }
+    }
+
+    /// Expected value of standing on `total` (1 unit, non-natural) against these outcomes.
+    pub fn stand_ev(&self, total: u32) -> f64 {
+        if total > 21 {
+            return -1.0;
+        }
+
+        let mut ev: f64 = self.bust() - self.blackjack();
+        for dealer_total in 17..=21 {
+            match total.cmp(&dealer_total) {
+                std::cmp::Ordering::Greater => ev += self.total(dealer_total),
+                std::cmp::Ordering::Less => ev -= self.total(dealer_total),
+                std::cmp::Ordering::Equal => {},
+            }
+        }
+        ev
+    }
+}
+
+/// Dealer outcomes for an infinite shoe. In peek games the player only acts once the dealer has no blackjack,
+/// so the hole card is conditioned on not completing one.
+pub fn dealer_outcomes(up_card: u32, rules: &TableRules) -> DealerOutcomes {
+    let conditioned: bool = rules.hole_card == HoleCard::Peek;
+    let (total, soft) = add_card(0, false, up_card);
+    let mut outcomes: DealerOutcomes = DealerOutcomes::default();
+
+    let excluded: Option<u32> = match (conditioned, up_card) {
+        (true, 10) => Some(11),
+        (true, 11) => Some(10),
+        _ => None,
+    };
+    let remaining: f64 = 1.0 - excluded.map(card_probability).unwrap_or(0.0);
+
+    for value in CARD_VALUES {
+        if Some(value) == excluded {
+            continue;
+        }
+        let (next_total, next_soft) = add_card(total, soft, value);
+        let branch: DealerOutcomes = match next_total == 21 {
+            true => {
+                let mut blackjack: DealerOutcomes = DealerOutcomes::default();
+                blackjack.0[DealerOutcomes::BLACKJACK] = 1.0;
+                blackjack
+            },
+            false => dealer_from(next_total, next_soft, rules.hit_soft_17),
+        };
+        outcomes.add(&branch, card_probability(value) / remaining);
+    }
+
+    outcomes
+}
+
+fn dealer_from(total: u32, soft: bool, hit_soft_17: bool) -> DealerOutcomes {
+    let mut outcomes: DealerOutcomes = DealerOutcomes::default();
+
+    if total > 21 {
+        outcomes.0[DealerOutcomes::BUST] = 1.0;
+        return outcomes;
+    }
+    if total > 17 || (total == 17 && (!hit_soft_17 || !soft)) {
+        outcomes.0[total as usize - 17] = 1.0;
+        return outcomes;
+    }
+
+    for value in CARD_VALUES {
+        let (next_total, next_soft) = add_card(total, soft, value);
+        outcomes.add(&dealer_from(next_total, next_soft, hit_soft_17), card_probability(value));
+    }
+
+    outcomes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq, Ord)]
+pub enum HandKind {
+    Hard(u32),
+    Soft(u32),
+    Pair(u32),
+}
+
+impl HandKind {
+    pub fn of(hand: &Hand) -> Self {
+        let value: HandValue = hand.value();
+        match (hand.can_split(), value.soft) {
+            (true, _) => Self::Pair(hand[0].value()),
+            (false, true) => Self::Soft(value.total),
+            (false, false) => Self::Hard(value.total),
+        }
+    }
+}
+
+/// Expected value (in units of the original bet) of each action for one hand against one up-card.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActionValues {
+    pub stand: f64,
+    pub hit: f64,
+    pub double: f64,
+    pub split: Option<f64>,
+    pub surrender: Option<f64>,
+}
+
+impl ActionValues {
+    pub fn value(&self, action: Action) -> Option<f64> {
+        match action {
+            Action::Stand => Some(self.stand),
+            Action::Hit => Some(self.hit),
+            Action::Double => Some(self.double),
+            Action::Split => self.split,
+            Action::Surrender => self.surrender,
+            _ => None,
+        }
+    }
+
+    /// The legal action with the highest expected value.
+    pub fn best(&self, actions: &[Action]) -> Action {
+        actions
+            .iter()
+            .filter_map(|action: &Action| self.value(*action).map(|ev: f64| (*action, ev)))
+            .fold((Action::Stand, f64::MIN), |best: (Action, f64), candidate: (Action, f64)| {
+                match candidate.1 > best.1 {
+                    true => candidate,
+                    false => best,
+                }
+            })
+            .0
+    }
+}
+
+struct Evaluator<'a> {
+    rules: &'a TableRules,
+    outcomes: DealerOutcomes,
+    hit_memo: HashMap<(u32, bool), f64>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(up_card: u32, rules: &'a TableRules) -> Self {
+        Self { rules, outcomes: dealer_outcomes(up_card, rules), hit_memo: HashMap::new() }
+    }
+
+    /// Under ENHC with original bets only, a dealer blackjack only takes one unit of a doubled or split wager.
+    fn original_bets_refund(&self) -> f64 {
+        match self.rules.hole_card == HoleCard::NoHoleCard && self.rules.original_bets_only {
+            true => self.outcomes.blackjack(),
+            false => 0.0,
+        }
+    }
+
+    fn stand(&self, total: u32) -> f64 {
+        self.outcomes.stand_ev(total)
+    }
+
+    fn hit(&mut self, total: u32, soft: bool) -> f64 {
+        if let Some(ev) = self.hit_memo.get(&(total, soft)) {
+            return *ev;
+        }
+
+        let mut ev: f64 = 0.0;
+        for value in CARD_VALUES {
+            let (next_total, next_soft) = add_card(total, soft, value);
+            let outcome: f64 = match next_total > 21 {
+                true => -1.0,
+                false => self.stand(next_total).max(self.hit(next_total, next_soft)),
+            };
+            ev += card_probability(value) * outcome;
+        }
+
+        self.hit_memo.insert((total, soft), ev);
+        ev
+    }
+
+    fn double(&self, total: u32, soft: bool) -> f64 {
+        let mut ev: f64 = 0.0;
+        for value in CARD_VALUES {
+            let (next_total, _) = add_card(total, soft, value);
+            ev += card_probability(value) * self.stand(next_total);
+        }
+        2.0 * ev + self.original_bets_refund()
+    }
+
+    fn surrender(&self) -> f64 {
+        match self.rules.hole_card {
+            HoleCard::Peek => -0.5,
+            HoleCard::NoHoleCard => -0.5 * (1.0 - self.outcomes.blackjack()) - self.outcomes.blackjack(),
+        }
+    }
+
+    /// Splits once without resplitting; split Aces receive one card.
+    fn split(&mut self, pair: u32) -> f64 {
+        let (total, soft) = add_card(0, false, pair);
+        let mut ev: f64 = 0.0;
+
+        for value in CARD_VALUES {
+            let (next_total, next_soft) = add_card(total, soft, value);
+            let outcome: f64 = match pair == 11 {
+                true => self.stand(next_total),
+                false => {
+                    let mut best: f64 = self.stand(next_total).max(self.hit(next_total, next_soft));
+                    if self.rules.double_after_split {
+                        best = best.max(self.double(next_total, next_soft) - self.original_bets_refund());
+                    }
+                    best
+                },
+            };
+            ev += card_probability(value) * outcome;
+        }
+
+        2.0 * ev + self.original_bets_refund()
+    }
+
+    fn values(&mut self, total: u32, soft: bool) -> ActionValues {
+        ActionValues {
+            stand: self.stand(total),
+            hit: self.hit(total, soft),
+            double: self.double(total, soft),
+            split: None,
+            surrender: match self.rules.surrender {
+                Surrender::None => None,
+                Surrender::Late | Surrender::Early => Some(self.surrender()),
+            },
+        }
+    }
+}
+
+/// Basic strategy for a rule set, derived from infinite-deck dealer probabilities rather than a fixed chart.
+#[derive(Debug, Clone)]
+pub struct StrategyTable {
+    rules: TableRules,
+    totals: BTreeMap<(HandKind, u32), ActionValues>,
+    splits: BTreeMap<(u32, u32), f64>,
+    dealer: BTreeMap<u32, DealerOutcomes>,
+}
+
+impl StrategyTable {
+    pub fn new(rules: &TableRules) -> Self {
+        let mut totals: BTreeMap<(HandKind, u32), ActionValues> = BTreeMap::new();
+        let mut splits: BTreeMap<(u32, u32), f64> = BTreeMap::new();
+        let mut dealer: BTreeMap<u32, DealerOutcomes> = BTreeMap::new();
+
+        for up_card in CARD_VALUES {
+            let mut evaluator: Evaluator = Evaluator::new(up_card, rules);
+            dealer.insert(up_card, evaluator.outcomes);
+
+            for total in 4..=21 {
+                totals.insert((HandKind::Hard(total), up_card), evaluator.values(total, false));
+            }
+            for total in 12..=21 {
+                totals.insert((HandKind::Soft(total), up_card), evaluator.values(total, true));
+            }
+            for pair in CARD_VALUES {
+                splits.insert((pair, up_card), evaluator.split(pair));
+            }
+        }
+
+        Self { rules: rules.clone(), totals, splits, dealer }
+    }
+
+    pub fn rules(&self) -> &TableRules {
+        &self.rules
+    }
+
+    pub fn dealer_outcomes(&self, up_card: u32) -> Option<&DealerOutcomes> {
+        self.dealer.get(&up_card)
+    }
+
+    /// Action values for a hand `kind` against `up_card`; pairs also carry their split value.
+    pub fn lookup(&self, kind: HandKind, up_card: u32) -> Option<ActionValues> {
+        match kind {
+            HandKind::Pair(pair) => {
+                let (total, soft) = add_card(0, false, pair);
+                let (total, soft) = add_card(total, soft, pair);
+                let kind: HandKind = if soft { HandKind::Soft(total) } else { HandKind::Hard(total) };
+                let mut values: ActionValues = self.lookup(kind, up_card)?;
+                values.split = self.splits.get(&(pair, up_card)).copied();
+                Some(values)
+            },
+            _ => self.totals.get(&(kind, up_card)).copied(),
+        }
+    }
+
+    pub fn values(&self, hand: &Hand, up_card: u32) -> Option<ActionValues> {
+        self.lookup(HandKind::of(hand), up_card)
+    }
+
+    pub fn decide(&self, seat_hand: &PlayerHand, up_card: u32, actions: &[Action]) -> Action {
+        match self.values(&seat_hand.hand, up_card) {
+            Some(values) => values.best(actions),
+            None => Action::Stand,
+        }
+    }
+
+    /// Basic strategy adjusted by `indices` at `true_count`, following the precedence of `IndexTable::deviation`.
+    pub fn decide_at_count(&self, seat_hand: &PlayerHand, up_card: u32, actions: &[Action], indices: &IndexTable, true_count: f64) -> Action {
+        let basic: Action = self.decide(seat_hand, up_card, actions);
+        match indices.deviation(&seat_hand.hand, up_card, true_count, actions, basic) {
+            Some(play) => play.action,
+            None => basic,
+        }
+    }
+
+    /// Early surrender is decided before the peek, so it is weighed against the unconditioned value of playing on.
+    pub fn early_surrender(&self, hand: &Hand, up_card: u32) -> bool {
+        let playing_on: f64 = match self.values(hand, up_card) {
+            Some(values) => [Some(values.stand), Some(values.hit), Some(values.double), values.split]
+                .into_iter()
+                .flatten()
+                .fold(f64::MIN, f64::max),
+            None => return false,
+        };
+
+        let blackjack: f64 = match up_card {
+            11 => card_probability(10),
+            10 => card_probability(11),
+            _ => 0.0,
+        };
+
+        -0.5 > -blackjack + (1.0 - blackjack) * playing_on
+    }
+}
@@ -24,4 +24,919 @@ fn test_hiding() {
     println!("hidden: {card}");
     card.reveal();
     println!("revealed: {card}");
-}
\ No newline at end of file
+}
+
+#[test]
+fn split_hand_test() {
+    let eight: Card = Card { suit: Suit::Hearts, rank: Rank::Eight, hidden: false };
+    let mut hand: Hand = Hand(vec![eight, Card { suit: Suit::Spades, ..eight }], false);
+    assert!(hand.can_split());
+
+    let second: Hand = hand.split().unwrap();
+    assert_eq!(hand.0.len(), 1);
+    assert_eq!(second.0.len(), 1);
+    assert!(!hand.can_split());
+    assert!(hand.split().is_err());
+
+    let king: Card = Card { suit: Suit::Clubs, rank: Rank::King, hidden: false };
+    let queen: Card = Card { suit: Suit::Clubs, rank: Rank::Queen, hidden: false };
+    assert!(Hand(vec![king, queen], false).can_split());
+}
+
+#[test]
+fn table_rules_test() {
+    assert_eq!(TableRules::default(), TableRules::vegas_strip());
+    assert_eq!(TableRules::presets().len(), 4);
+    assert!(TableRules::downtown().hit_soft_17);
+    assert_eq!(TableRules::european().hole_card, HoleCard::NoHoleCard);
+
+    assert_eq!(BlackjackPayout::ThreeToTwo.ratio(), (3, 2));
+    assert_eq!(BlackjackPayout::SixToFive.ratio(), (6, 5));
+    assert_eq!(BlackjackPayout::EvenMoney.ratio(), (1, 1));
+}
+
+#[test]
+fn payout_test() {
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    let king: Card = Card { suit: Suit::Hearts, rank: Rank::King, hidden: false };
+    let nine: Card = Card { suit: Suit::Clubs, rank: Rank::Nine, hidden: false };
+    let mut rules: TableRules = TableRules::default();
+
+    let natural: PlayerHand = PlayerHand::new(Hand(vec![ace, king], false), Bet(vec![Chip::C5]));
+    let dealer: Hand = Hand(vec![king, nine], false);
+    assert_eq!(settle(&natural, &dealer, &rules), Settlement::new(Outcome::Blackjack, 5, 7));
+
+    rules.chip_rounding = ChipRounding::Up;
+    assert_eq!(natural_winnings(5, &rules), 8);
+    rules.blackjack_payout = BlackjackPayout::SixToFive;
+    assert_eq!(natural_winnings(10, &rules), 12);
+
+    let dealer_natural: Hand = Hand(vec![king, ace], false);
+    assert_eq!(settle(&natural, &dealer_natural, &rules).outcome, Outcome::Push);
+
+    let mut doubled: PlayerHand = PlayerHand::new(Hand(vec![nine, nine], false), Bet(vec![Chip::C10]));
+    doubled.double_down();
+    assert_eq!(settle(&doubled, &dealer, &rules), Settlement::new(Outcome::Loss, 20, -20));
+
+    let mut balance: Balance = Balance(vec![Chip::C100]);
+    Settlement::new(Outcome::Win, 20, 20).apply(&mut balance);
+    assert_eq!(balance.sum(), 120);
+}
+
+#[test]
+fn insurance_test() {
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    let king: Card = Card { suit: Suit::Hearts, rank: Rank::King, hidden: false };
+    let seven: Card = Card { suit: Suit::Clubs, rank: Rank::Seven, hidden: false };
+    let rules: TableRules = TableRules::default();
+
+    let mut seat_hand: PlayerHand = PlayerHand::new(Hand(vec![king, seven], false), Bet(vec![Chip::C10, Chip::C5]));
+    assert_eq!(max_insurance(&seat_hand), 7);
+    seat_hand.insurance = 5;
+    assert_eq!(committed_bets(std::slice::from_ref(&seat_hand)), 20);
+
+    let dealer_natural: Hand = Hand(vec![ace, king], false);
+    let settlements: Vec<Vec<Settlement>> = settle_round(&[vec![seat_hand]], &dealer_natural, &rules);
+    assert_eq!(settlements[0], vec![
+        Settlement::new(Outcome::Loss, 15, -15),
+        Settlement::new(Outcome::Insurance, 5, 10),
+    ]);
+    assert_eq!(settle_insurance(5, &Hand(vec![ace, seven], false)).net, -5);
+
+    let mut natural: PlayerHand = PlayerHand::new(Hand(vec![ace, king], false), Bet(vec![Chip::C10]));
+    natural.even_money = true;
+    assert_eq!(settle(&natural, &dealer_natural, &rules), Settlement::new(Outcome::EvenMoney, 10, 10));
+}
+
+#[test]
+fn surrender_test() {
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    let king: Card = Card { suit: Suit::Hearts, rank: Rank::King, hidden: false };
+    let six: Card = Card { suit: Suit::Clubs, rank: Rank::Six, hidden: false };
+    let mut rules: TableRules = TableRules::atlantic_city();
+
+    let mut seat_hand: PlayerHand = PlayerHand::new(Hand(vec![king, six], false), Bet(vec![Chip::C10, Chip::C5]));
+    assert!(seat_hand.can_surrender());
+    seat_hand.surrendered = true;
+
+    let dealer_natural: Hand = Hand(vec![ace, king], false);
+    assert_eq!(settle(&seat_hand, &dealer_natural, &rules), Settlement::new(Outcome::Surrender, 15, -8));
+    rules.chip_rounding = ChipRounding::Up;
+    assert_eq!(settle(&seat_hand, &dealer_natural, &rules), Settlement::new(Outcome::Surrender, 15, -7));
+
+    seat_hand.split = true;
+    assert!(!seat_hand.can_surrender());
+}
+
+#[test]
+fn dealer_hole_card_test() {
+    let mut deck: Deck = Deck::build(1);
+    let mut dealer: Dealer = Dealer::new();
+
+    dealer.deal(&mut deck, &TableRules::vegas_strip()).unwrap();
+    assert_eq!(dealer.hole_card(), HoleCardState::Hidden);
+    assert!(dealer.hand()[1].is_hidden());
+
+    dealer.reveal();
+    assert_eq!(dealer.hole_card(), HoleCardState::Revealed);
+    assert!(!dealer.hand()[1].is_hidden());
+
+    dealer.deal(&mut deck, &TableRules::european()).unwrap();
+    assert_eq!(dealer.hole_card(), HoleCardState::NotDealt);
+    assert_eq!(dealer.hand().0.len(), 1);
+    assert!(!dealer.can_peek());
+
+    dealer.play(&mut deck, &TableRules::european()).unwrap();
+    assert_eq!(dealer.hole_card(), HoleCardState::Revealed);
+    assert!(dealer.hand().0.len() >= 2);
+}
+
+#[test]
+fn original_bets_only_test() {
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    let king: Card = Card { suit: Suit::Hearts, rank: Rank::King, hidden: false };
+    let five: Card = Card { suit: Suit::Clubs, rank: Rank::Five, hidden: false };
+    let dealer_natural: Hand = Hand(vec![ace, king], false);
+
+    let mut doubled: PlayerHand = PlayerHand::new(Hand(vec![five, five, king], false), Bet(vec![Chip::C10]));
+    doubled.double_down();
+    let mut split: PlayerHand = PlayerHand::new(Hand(vec![five, king], false), Bet(vec![Chip::C10]));
+    split.split = true;
+    let seat: Vec<PlayerHand> = vec![doubled, split];
+
+    let mut rules: TableRules = TableRules::european();
+    rules.original_bets_only = true;
+    let settlements: Vec<Vec<Settlement>> = settle_round(std::slice::from_ref(&seat), &dealer_natural, &rules);
+    assert_eq!(settlements[0], vec![
+        Settlement::new(Outcome::Loss, 20, -10),
+        Settlement::new(Outcome::Push, 10, 0),
+    ]);
+
+    rules.original_bets_only = false;
+    let settlements: Vec<Vec<Settlement>> = settle_round(&[seat], &dealer_natural, &rules);
+    assert_eq!(settlements[0], vec![
+        Settlement::new(Outcome::Loss, 20, -20),
+        Settlement::new(Outcome::Loss, 10, -10),
+    ]);
+}
+
+#[test]
+fn shoe_test() {
+    let mut shoe: Shoe = Shoe::new(1, 0.75);
+    assert_eq!(shoe.remaining(), 51);
+    assert!(shoe.burned().is_some());
+    assert_eq!(shoe.discard_tray().len(), 1);
+    // The burned card goes into the tray face down, so nobody has seen it.
+    assert!(shoe.discards_seen().is_empty());
+    assert!(!shoe.cut_card_reached());
+
+    let mut hand: Hand = Hand(vec![], false);
+    while !shoe.cut_card_reached() {
+        hand.draw_from_hidden(shoe.deck_mut()).unwrap();
+    }
+    assert_eq!(shoe.remaining(), 13);
+
+    shoe.discard(&hand);
+    assert_eq!(shoe.discard_tray().len(), 39);
+    assert!(shoe.discard_tray().iter().all(|card: &Card| !card.is_hidden()));
+
+    let last: Hand = Hand(vec![shoe.draw().unwrap()], false);
+    shoe.discard(&last);
+    assert!(shoe.reshuffle_discards());
+    assert_eq!(shoe.remaining(), 52);
+    assert!(shoe.discard_tray().is_empty() && shoe.burned().is_none());
+    assert!(!shoe.reshuffle_discards());
+
+    shoe.shuffle();
+    assert_eq!(shoe.remaining(), 51);
+    assert_eq!(shoe.discard_tray().len(), 1);
+}
+
+#[test]
+fn seeded_shuffle_test() {
+    let mut first: Deck = Deck::build(2);
+    let mut second: Deck = Deck::build(2);
+    first.shuffle_with_seed(42);
+    second.shuffle_with_seed(42);
+    assert_eq!(first.cards(), second.cards());
+
+    let mut first: Shoe = Shoe::with_seed(6, 0.75, 7);
+    let mut second: Shoe = Shoe::with_seed(6, 0.75, 7);
+    first.shuffle();
+    second.shuffle();
+    assert_eq!(first.deck().cards(), second.deck().cards());
+    assert_eq!(first.seed(), 7);
+}
+
+#[test]
+fn hand_value_test() {
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    let nine: Card = Card { suit: Suit::Clubs, rank: Rank::Nine, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let king: Card = Card { suit: Suit::Hearts, rank: Rank::King, hidden: false };
+
+    let value: HandValue = Hand(vec![ace, ace, nine], false).value();
+    assert_eq!(value, HandValue { total: 21, soft: true, blackjack: false, bust: false });
+
+    let value: HandValue = Hand(vec![ace, ace, nine, king], false).value();
+    assert_eq!(value, HandValue { total: 21, soft: false, blackjack: false, bust: false });
+
+    let value: HandValue = Hand(vec![ace, ace, ace, ace], false).value();
+    assert_eq!(value, HandValue { total: 14, soft: true, blackjack: false, bust: false });
+
+    assert!(Hand(vec![ace, king], false).value().blackjack);
+    assert!(Hand(vec![king, six, nine], false).value().bust);
+
+    let soft_seventeen: Hand = Hand(vec![ace, six], false);
+    let mut deck: Deck = Deck(vec![king]);
+    let mut dealer: Hand = soft_seventeen.clone();
+    dealer_logic(&mut deck, &mut dealer, false).unwrap();
+    assert_eq!(dealer.0.len(), 2);
+
+    let mut dealer: Hand = soft_seventeen;
+    dealer_logic(&mut deck, &mut dealer, true).unwrap();
+    assert_eq!(dealer.value(), HandValue { total: 17, soft: false, blackjack: false, bust: false });
+
+    let hard_nineteen: Hand = Hand(vec![king, nine], false);
+    assert_eq!(hard_nineteen.compare_to(&Hand(vec![ace, ace, six], false)).unwrap(), std::cmp::Ordering::Greater);
+}
+
+#[test]
+fn engine_round_test() {
+    let rules: TableRules = TableRules::default();
+    let players: Table = Table(vec![
+        Player(0, Balance(100.into_chips()), Bet(vec![]), true),
+        Player(1, Balance(100.into_chips()), Bet(vec![]), true),
+    ]);
+    let mut engine: BlackjackEngine = BlackjackEngine::new(rules.clone(), Shoe::from_rules_with_seed(&rules, 3), players);
+
+    let events: Vec<Event> = engine.start_round().unwrap();
+    assert!(events.contains(&Event::RoundStarted { round: 1 }));
+    assert_eq!(engine.decision(), Some(Decision::Bet { seat: 0, balance: 100 }));
+    assert!(matches!(engine.apply(Action::Hit), Err(DeckError::IllegalAction)));
+    assert!(matches!(engine.apply(Action::Bet(101)), Err(DeckError::InsufficientBalance)));
+
+    engine.apply(Action::Bet(10)).unwrap();
+    let mut events: Vec<Event> = engine.apply(Action::Bet(20)).unwrap();
+    assert!(events.contains(&Event::Dealt));
+
+    while let Some(decision) = engine.decision() {
+        let action: Action = match decision {
+            Decision::Play { .. } => Action::Stand,
+            _ => Action::Decline,
+        };
+        events.extend(engine.apply(action).unwrap());
+    }
+
+    assert_eq!(engine.phase(), Phase::Finished);
+    assert_eq!(events.last(), Some(&Event::RoundOver { round: 1 }));
+
+    for seat in 0..2 {
+        let net: i64 = engine.settlements()[seat].iter().map(|settlement: &Settlement| settlement.net).sum();
+        assert_eq!(engine.players().0[seat].get_balance() as i64, 100 + net);
+    }
+    assert!(engine.start_round().is_ok());
+}
+
+/// Deals one seat betting `bet` from successive seeded shoes and returns the first engine, with the events of its opening
+/// deal, whose deal passes `deal`.
+fn seeded_deal(rules: &TableRules, bet: u32, deal: impl Fn(&BlackjackEngine) -> bool) -> (BlackjackEngine, Vec<Event>) {
+    (0..100_000)
+        .map(|seed: u64| {
+            let players: Table = Table(vec![Player(0, Balance(1000.into_chips()), Bet(vec![]), true)]);
+            let mut engine: BlackjackEngine = BlackjackEngine::new(rules.clone(), Shoe::from_rules_with_seed(rules, seed), players);
+            let mut events: Vec<Event> = engine.start_round().unwrap();
+            events.extend(engine.apply(Action::Bet(bet)).unwrap());
+            (engine, events)
+        })
+        .find(|(engine, _): &(BlackjackEngine, Vec<Event>)| deal(engine))
+        .expect("no seed deals the hand")
+}
+
+fn up_card(engine: &BlackjackEngine) -> u32 {
+    engine.dealer().up_card().map(|card: Card| card.value()).unwrap_or(0)
+}
+
+fn next_card(engine: &BlackjackEngine) -> u32 {
+    engine.shoe().deck().cards().last().map(Card::value).unwrap_or(0)
+}
+
+fn can_play(engine: &BlackjackEngine, action: Action) -> bool {
+    matches!(engine.decision(), Some(Decision::Play { seat: 0, hand: 0, actions }) if actions.contains(&action))
+}
+
+fn dealt_pair(engine: &BlackjackEngine, value: u32) -> bool {
+    engine.hands()[0].first().is_some_and(|seat_hand: &PlayerHand| seat_hand.hand.0.iter().all(|card: &Card| card.value() == value))
+}
+
+fn hole_card(engine: &BlackjackEngine) -> u32 {
+    engine.dealer().hand().0.get(1).map(Card::value).unwrap_or(0)
+}
+
+/// Stands on every hand and declines everything else until the round is over.
+fn finish_round(engine: &mut BlackjackEngine) -> Vec<Event> {
+    let mut events: Vec<Event> = Vec::new();
+    while let Some(decision) = engine.decision() {
+        let action: Action = match decision {
+            Decision::Play { .. } => Action::Stand,
+            _ => Action::Decline,
+        };
+        events.extend(engine.apply(action).unwrap());
+    }
+    events
+}
+
+fn settled_balance(engine: &BlackjackEngine) -> i64 {
+    1000 + engine.settlements()[0].iter().map(|settlement: &Settlement| settlement.net).sum::<i64>()
+}
+
+#[test]
+fn engine_split_test() {
+    // One split allowed: the first hand draws another eight and can't be split again.
+    let rules: TableRules = TableRules { max_splits: 1, ..TableRules::vegas_strip() };
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| {
+        dealt_pair(engine, 8) && next_card(engine) == 8 && can_play(engine, Action::Split)
+    });
+
+    let events: Vec<Event> = engine.apply(Action::Split).unwrap();
+    assert_eq!(events[0], Event::Split { seat: 0, hand: 0 });
+    assert!(matches!(events[1], Event::CardDrawn { seat: 0, hand: 0, .. }));
+    assert_eq!(engine.hands()[0].len(), 2);
+    assert!(engine.hands()[0].iter().all(|seat_hand: &PlayerHand| seat_hand.split && seat_hand.bet.sum() == 10));
+    assert!(dealt_pair(&engine, 8));
+    assert!(!can_play(&engine, Action::Split));
+
+    let events: Vec<Event> = finish_round(&mut engine);
+    assert!(events.contains(&Event::CardDrawn { seat: 0, hand: 1, card: engine.hands()[0][1].hand[1] }));
+    assert_eq!(engine.settlements()[0].len(), 2);
+    assert_eq!(engine.players().0[0].get_balance() as i64, settled_balance(&engine));
+
+    // Split aces get one card each and the round moves straight on to the dealer.
+    let (mut engine, _) = seeded_deal(&TableRules::vegas_strip(), 10, |engine: &BlackjackEngine| {
+        dealt_pair(engine, 11) && can_play(engine, Action::Split)
+    });
+    let events: Vec<Event> = engine.apply(Action::Split).unwrap();
+    assert!(events.contains(&Event::DealerPlayed));
+    assert_eq!(engine.phase(), Phase::Finished);
+    assert!(engine.hands()[0].iter().all(|seat_hand: &PlayerHand| seat_hand.is_split_aces() && seat_hand.hand.0.len() == 2));
+    assert!(engine.settlements()[0].iter().all(|settlement: &Settlement| settlement.outcome != Outcome::Blackjack));
+}
+
+#[test]
+fn engine_double_and_surrender_test() {
+    let rules: TableRules = TableRules::atlantic_city();
+    let eleven = |engine: &BlackjackEngine| engine.hands()[0].first().is_some_and(|seat_hand: &PlayerHand| seat_hand.hand.sum() == 11);
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| eleven(engine) && can_play(engine, Action::Double));
+
+    let events: Vec<Event> = engine.apply(Action::Double).unwrap();
+    assert!(matches!(events[0], Event::Doubled { seat: 0, hand: 0, .. }));
+    assert!(events.contains(&Event::DealerPlayed));
+    let seat_hand: &PlayerHand = &engine.hands()[0][0];
+    assert!(seat_hand.doubled && seat_hand.hand.0.len() == 3 && seat_hand.bet.sum() == 20);
+    assert_eq!(engine.settlements()[0][0].wager, 20);
+    assert_eq!(engine.players().0[0].get_balance() as i64, settled_balance(&engine));
+
+    // Late surrender after the peek gives back half the bet.
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| can_play(engine, Action::Surrender));
+    let events: Vec<Event> = engine.apply(Action::Surrender).unwrap();
+    assert_eq!(events[0], Event::Surrendered { seat: 0, hand: 0 });
+    assert_eq!(engine.settlements()[0], vec![Settlement::new(Outcome::Surrender, 10, -5)]);
+
+    // Early surrender comes before the peek, so it still saves half the bet against a natural.
+    let early: TableRules = TableRules { surrender: Surrender::Early, ..TableRules::vegas_strip() };
+    let (mut engine, _) = seeded_deal(&early, 10, |engine: &BlackjackEngine| up_card(engine) == 10 && hole_card(engine) == 11);
+    assert_eq!(engine.decision(), Some(Decision::EarlySurrender { seat: 0 }));
+    let events: Vec<Event> = engine.apply(Action::Surrender).unwrap();
+    assert_eq!(events[0], Event::EarlySurrendered { seat: 0 });
+    assert!(events.contains(&Event::DealerPeeked { blackjack: true }));
+    assert_eq!(engine.settlements()[0], vec![Settlement::new(Outcome::Surrender, 10, -5)]);
+}
+
+#[test]
+fn engine_insurance_test() {
+    let rules: TableRules = TableRules::vegas_strip();
+    let natural = |engine: &BlackjackEngine| engine.hands()[0].first().is_some_and(|seat_hand: &PlayerHand| seat_hand.is_natural());
+
+    // Insurance against an Ace with a ten underneath pays 2:1 and the peek ends the round.
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| up_card(engine) == 11 && hole_card(engine) == 10 && !natural(engine));
+    assert_eq!(engine.decision(), Some(Decision::Insurance { seat: 0, max: 5 }));
+    assert!(matches!(engine.apply(Action::Insurance(6)), Err(DeckError::InsufficientBalance)));
+    let events: Vec<Event> = engine.apply(Action::Insurance(5)).unwrap();
+    assert_eq!(events[0], Event::InsuranceTaken { seat: 0, amount: 5 });
+    assert!(events.contains(&Event::DealerPeeked { blackjack: true }));
+    assert_eq!(engine.phase(), Phase::Finished);
+    assert_eq!(engine.settlements()[0], vec![
+        Settlement::new(Outcome::Loss, 10, -10),
+        Settlement::new(Outcome::Insurance, 5, 10),
+    ]);
+    assert_eq!(engine.players().0[0].get_balance(), 1000);
+
+    // Even money is offered on a natural instead, and pays 1:1 whatever the dealer has.
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| up_card(engine) == 11 && natural(engine));
+    assert_eq!(engine.decision(), Some(Decision::EvenMoney { seat: 0 }));
+    let events: Vec<Event> = engine.apply(Action::EvenMoney).unwrap();
+    assert_eq!(events[0], Event::EvenMoneyTaken { seat: 0 });
+    assert_eq!(engine.settlements()[0], vec![Settlement::new(Outcome::EvenMoney, 10, 10)]);
+
+    // A peeked natural under a ten ends the round as soon as the bets are in.
+    let (engine, events) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| up_card(engine) == 10 && hole_card(engine) == 11 && !natural(engine));
+    assert!(events.contains(&Event::DealerPeeked { blackjack: true }));
+    assert_eq!(engine.phase(), Phase::Finished);
+    assert_eq!(engine.settlements()[0], vec![Settlement::new(Outcome::Loss, 10, -10)]);
+}
+
+#[test]
+fn surrender_against_natural_test() {
+    // Without a peek the dealer's natural only shows after a late surrender, and then it takes the whole bet.
+    let rules: TableRules = TableRules { surrender: Surrender::Late, ..TableRules::european() };
+    let (mut engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| {
+        up_card(engine) == 10 && next_card(engine) == 11 && can_play(engine, Action::Surrender)
+    });
+
+    let events: Vec<Event> = engine.apply(Action::Surrender).unwrap();
+    assert!(events.contains(&Event::Surrendered { seat: 0, hand: 0 }));
+    assert!(engine.dealer().has_blackjack());
+    assert_eq!(engine.settlements()[0], vec![Settlement::new(Outcome::Surrender, 10, -10)]);
+    assert_eq!(engine.players().0[0].get_balance(), 990);
+}
+
+#[test]
+fn controller_test() {
+    let script: ScriptedController = ScriptedController::parse("# opening\nbet 25\nhit\n\nstand # done\ninsurance 5\n").unwrap();
+    assert_eq!(script.remaining(), 4);
+    assert!(ScriptedController::parse("fly away").is_err());
+
+    let ten: Card = Card { suit: Suit::Spades, rank: Rank::Ten, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let eight: Card = Card { suit: Suit::Clubs, rank: Rank::Eight, hidden: false };
+    let ace: Card = Card { suit: Suit::Clubs, rank: Rank::Ace, hidden: false };
+    let actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double, Action::Split, Action::Surrender];
+
+    let table: StrategyTable = StrategyTable::new(&TableRules::atlantic_city());
+    let hard_sixteen: PlayerHand = PlayerHand::new(Hand(vec![ten, six], false), Bet(vec![Chip::C10]));
+    assert_eq!(table.decide(&hard_sixteen, 6, &actions), Action::Stand);
+    assert_eq!(table.decide(&hard_sixteen, 10, &actions), Action::Surrender);
+    assert_eq!(table.decide(&hard_sixteen, 10, &actions[..3]), Action::Hit);
+
+    let eights: PlayerHand = PlayerHand::new(Hand(vec![eight, eight], false), Bet(vec![Chip::C10]));
+    assert_eq!(table.decide(&eights, 10, &actions), Action::Split);
+
+    let soft_eighteen: PlayerHand = PlayerHand::new(Hand(vec![ace, Card { rank: Rank::Seven, ..six }], false), Bet(vec![Chip::C10]));
+    assert_eq!(table.decide(&soft_eighteen, 5, &actions), Action::Double);
+    assert_eq!(table.decide(&soft_eighteen, 5, &actions[..2]), Action::Stand);
+    assert_eq!(table.decide(&soft_eighteen, 10, &actions), Action::Hit);
+}
+
+#[test]
+fn strategy_test() {
+    let rules: TableRules = TableRules::vegas_strip();
+    for up_card in 2..=11 {
+        let outcomes: DealerOutcomes = dealer_outcomes(up_card, &rules);
+        assert!((outcomes.0.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+    assert_eq!(dealer_outcomes(10, &rules).blackjack(), 0.0);
+    assert!(dealer_outcomes(6, &rules).bust() > 0.42 && dealer_outcomes(6, &rules).bust() < 0.43);
+
+    let stand: StrategyTable = StrategyTable::new(&rules);
+    let hit: StrategyTable = StrategyTable::new(&TableRules { hit_soft_17: true, ..rules.clone() });
+    let actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double, Action::Split];
+    let best = |table: &StrategyTable, kind: HandKind, up_card: u32| table.lookup(kind, up_card).unwrap().best(&actions);
+
+    assert_eq!(best(&stand, HandKind::Hard(12), 3), Action::Hit);
+    assert_eq!(best(&stand, HandKind::Hard(12), 4), Action::Stand);
+    assert_eq!(best(&stand, HandKind::Hard(16), 10), Action::Hit);
+    assert_eq!(best(&stand, HandKind::Hard(11), 6), Action::Double);
+    assert_eq!(best(&stand, HandKind::Soft(18), 2), Action::Stand);
+    assert_eq!(best(&hit, HandKind::Soft(18), 2), Action::Double);
+    assert_eq!(best(&stand, HandKind::Pair(11), 11), Action::Split);
+    assert_eq!(best(&stand, HandKind::Pair(10), 6), Action::Stand);
+    assert_eq!(best(&stand, HandKind::Pair(5), 6), Action::Double);
+    assert_eq!(best(&stand, HandKind::Soft(13), 6), Action::Double);
+}
+
+#[test]
+fn chart_test() {
+    let table: StrategyTable = StrategyTable::new(&TableRules::atlantic_city());
+    let optimal: Chart = Chart::from_table(&table);
+    assert_eq!(Chart::parse(&optimal.to_string()).unwrap(), optimal);
+    assert_eq!(optimal.play(HandKind::Hard(16), 10), Some(Play::SurrenderOrHit));
+    assert!(optimal.deviations(&table).is_empty());
+
+    let custom: Chart = Chart::parse("Hand,2,3,4,5,6,7,8,9,10,A\nH16 S S S S S S S S S S # never bust\nP8,P,P,P,P,P,P,P,P,P,H\n").unwrap();
+    let deviations: Vec<Deviation> = custom.deviations(&table);
+    assert_eq!(deviations.len(), 6);
+    assert!(deviations.iter().all(|deviation: &Deviation| deviation.cost > 0.0));
+    assert!(Chart::parse("H16,S,S").is_err());
+    assert!(Chart::parse("X9,H,H,H,H,H,H,H,H,H,H").is_err());
+    assert!(Chart::parse("é9,H,H,H,H,H,H,H,H,H,H").is_err());
+    assert!(IndexTable::parse("Ü16 10 >=0 S").is_err());
+    assert_eq!(Chart::parse(&format!("\u{FEFF}{}", optimal)).unwrap(), optimal);
+
+    let ten: Card = Card { suit: Suit::Spades, rank: Rank::Ten, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let eight: Card = Card { suit: Suit::Clubs, rank: Rank::Eight, hidden: false };
+    let actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double, Action::Split];
+    assert_eq!(custom.decide(&Hand(vec![ten, six], false), 10, &actions), Some(Action::Stand));
+    assert_eq!(custom.decide(&Hand(vec![eight, eight], false), 11, &actions), Some(Action::Stand));
+    assert_eq!(custom.decide(&Hand(vec![eight, eight], false), 10, &actions), Some(Action::Split));
+    assert_eq!(custom.decide(&Hand(vec![ten, eight], false), 10, &actions), None);
+}
+
+#[test]
+fn counting_test() {
+    let names: Vec<&str> = systems().iter().map(|system| system.name()).collect();
+    assert_eq!(names, vec!["Hi-Lo", "KO", "Hi-Opt I", "Hi-Opt II", "Omega II", "Zen", "Wong Halves"]);
+    for system in systems() {
+        assert_eq!(system.is_balanced(), system.name() != "KO");
+    }
+    assert!(HiOptII.is_ace_neutral() && !Zen.is_ace_neutral());
+    assert_eq!(system_by_name("omega-ii").map(|system| system.name()), Some("Omega II"));
+    assert!(system_by_name("red seven").is_none());
+    assert_eq!(WongHalves.tag(Rank::Five), 1.5);
+
+    let deck: Deck = Deck::build(1);
+    let mut counter: Counter = Counter::new(Box::new(HiLo), 1);
+    counter.count_cards(deck.cards());
+    assert_eq!(counter.running_count(), 0.0);
+    assert_eq!(counter.aces_seen(), 4);
+    assert_eq!(counter.cards_seen(), 52);
+
+    let mut counter: Counter = Counter::new(Box::new(KO), 6);
+    assert_eq!(counter.running_count(), -20.0);
+    let five: Card = Card { suit: Suit::Hearts, rank: Rank::Five, hidden: false };
+    counter.count(&five);
+    counter.count(&Card { hidden: true, ..five });
+    assert_eq!(counter.running_count(), -19.0);
+    counter.reset();
+
+    let mut counter: Counter = Counter::new(Box::new(HiLo), 6);
+    let ace: Card = Card { suit: Suit::Spades, rank: Rank::Ace, hidden: false };
+    counter.count_cards(&[five, five, five, five, five, five, ace]);
+    assert_eq!(counter.running_count(), 5.0);
+    assert_eq!(counter.true_count(2.5), 2.0);
+    assert_eq!(counter.ace_surplus(3.0), 11.0);
+    assert!(counter.true_count_in(&Shoe::with_seed(6, 0.75, 1)) > 5.0 / 6.0);
+}
+
+#[test]
+fn training_test() {
+    let mut score: Score = Score::default();
+    assert_eq!(score.to_string(), "No checkpoints reached.");
+    score.record(true, std::time::Duration::from_secs(2));
+    score.record(false, std::time::Duration::from_secs(4));
+    assert_eq!(score.accuracy(), Some(0.5));
+    assert_eq!(score.average_time(), Some(std::time::Duration::from_secs(3)));
+    assert_eq!(score.to_string(), "Accuracy: 1/2 (50%)\tAverage answer time: 3.0s");
+
+    // Checkpoints come every 5 to 15 cards and always on the last card.
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::seed_from_u64(8);
+    let mut checkpoints: Checkpoints = Checkpoints::new(52, &mut rng);
+    let reached: Vec<u32> = (1..=52).filter(|dealt: &u32| checkpoints.reached(*dealt, &mut rng)).collect();
+    assert_eq!(reached.last(), Some(&52));
+    assert!((5..=15).contains(&reached[0]));
+    assert!(reached.windows(2).all(|pair: &[u32]| pair[1] - pair[0] <= 15 && (pair[1] - pair[0] >= 5 || pair[1] == 52)));
+
+    let five: Card = Card { suit: Suit::Clubs, rank: Rank::Five, hidden: false };
+    let mut counter: Counter = Counter::new(Box::new(HiLo), 1);
+    counter.count(&five);
+    counter.count(&five);
+    assert!(running_count_correct(Some(2.0), &counter));
+    assert!(!running_count_correct(Some(1.0), &counter) && !running_count_correct(None, &counter));
+
+    assert!(true_count_correct(Some(2.0), 2.4) && true_count_correct(Some(2.0), 1.5));
+    assert!(!true_count_correct(Some(2.0), 2.6) && !true_count_correct(None, 2.0));
+}
+
+#[test]
+fn indices_test() {
+    let ten: Card = Card { suit: Suit::Spades, rank: Rank::Ten, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let five: Card = Card { suit: Suit::Clubs, rank: Rank::Five, hidden: false };
+    let actions: Vec<Action> = vec![Action::Hit, Action::Stand, Action::Double, Action::Split, Action::Surrender];
+    let sixteen: PlayerHand = PlayerHand::new(Hand(vec![ten, six], false), Bet(vec![Chip::C10]));
+    let fifteen: PlayerHand = PlayerHand::new(Hand(vec![ten, five], false), Bet(vec![Chip::C10]));
+
+    let indices: IndexTable = IndexTable::standard();
+    assert_eq!(indices.plays.len(), 21);
+    assert!(indices.take_insurance(3.0) && !indices.take_insurance(2.9));
+
+    let table: StrategyTable = StrategyTable::new(&TableRules::vegas_strip());
+    assert_eq!(table.decide_at_count(&sixteen, 10, &actions[..4], &indices, -0.5), Action::Hit);
+    assert_eq!(table.decide_at_count(&sixteen, 10, &actions[..4], &indices, 0.0), Action::Stand);
+    assert_eq!(table.decide_at_count(&fifteen, 10, &actions[..4], &indices, 4.0), Action::Stand);
+    let eight: Card = Card { suit: Suit::Diamonds, rank: Rank::Eight, hidden: false };
+    let eights: PlayerHand = PlayerHand::new(Hand(vec![eight, eight], false), Bet(vec![Chip::C10]));
+    assert_eq!(table.decide_at_count(&eights, 10, &actions[..4], &indices, 6.0), Action::Split);
+    assert_eq!(table.decide_at_count(&eights, 9, &actions[..4], &indices, 6.0), Action::Split);
+
+    let late: StrategyTable = StrategyTable::new(&TableRules::atlantic_city());
+    assert_eq!(late.decide_at_count(&sixteen, 10, &actions, &indices, 2.0), Action::Surrender);
+    assert_eq!(late.decide_at_count(&fifteen, 10, &actions, &indices, 4.0), Action::Surrender);
+
+    let custom: IndexTable = IndexTable::parse("insurance >=+2.5 # late insurance\nP10 6 >=4 P\nH12 4 <0 H\n").unwrap();
+    assert_eq!(custom.plays[0], IndexPlay::new(HandKind::Pair(10), 6, Trigger::AtOrAbove(4.0), Action::Split));
+    assert_eq!(custom.plays[1].trigger, Trigger::Below(0.0));
+    assert!(custom.take_insurance(2.5));
+    let tens: Hand = Hand(vec![ten, ten], false);
+    assert_eq!(custom.lookup(&tens, 6, 4.0, &actions).map(|play: &IndexPlay| play.action), Some(Action::Split));
+    assert!(custom.lookup(&tens, 6, 3.9, &actions).is_none());
+    assert!(IndexTable::parse("H16 10 =0 S").is_err());
+    assert!(IndexTable::parse("H16 1 >=0 S").is_err());
+}
+
+#[test]
+fn simulation_test() {
+    let config: SimulationConfig = SimulationConfig { seats: 2, ..SimulationConfig::new(TableRules::vegas_strip(), 2000, 9) };
+    let stats: SimulationStats = simulate(&config).unwrap();
+    assert_eq!(stats, simulate(&config).unwrap());
+    assert_eq!(stats.rounds, 2000);
+    assert_eq!(stats.seat_rounds, 4000);
+    assert_eq!(stats.wins + stats.losses + stats.pushes, stats.hands);
+    assert_eq!(stats.hands, stats.seat_rounds + stats.splits);
+    assert!(stats.standard_deviation() > 0.9 && stats.standard_deviation() < 1.4);
+
+    let mut merged: SimulationStats = stats.clone();
+    merged.merge(&stats);
+    assert_eq!(merged.net, 2 * stats.net);
+    assert_eq!(merged.house_edge(), stats.house_edge());
+
+    let ramp: BetRamp = BetRamp::parse(10, "1, 2,4,8").unwrap();
+    assert_eq!((ramp.bet(-3.0), ramp.bet(1.9), ramp.bet(2.0), ramp.bet(12.0)), (10, 20, 40, 80));
+    assert!(BetRamp::parse(10, "1,0").is_none());
+}
+
+#[test]
+fn full_table_simulation_test() {
+    // A single deck can't hold seven seats past the cut card, so rounds shuffle the discards back in instead of failing.
+    let config: SimulationConfig = SimulationConfig { seats: 7, ..SimulationConfig::new(TableRules { decks: 1, ..TableRules::default() }, 300, 1) };
+    assert_eq!(simulate(&config).unwrap().seat_rounds, 2100);
+}
+
+#[test]
+fn parallel_simulation_test() {
+    let config: SimulationConfig = SimulationConfig::new(TableRules::downtown(), 1001, 4);
+    let stats: SimulationStats = simulate_parallel(&config, 3).unwrap();
+    assert_eq!(stats, simulate_parallel(&config, 3).unwrap());
+    assert_eq!(stats.rounds, 1001);
+
+    let mut merged: SimulationStats = SimulationStats { unit: 10, ..SimulationStats::default() };
+    for (index, rounds) in [334, 334, 333].into_iter().enumerate() {
+        let part: SimulationConfig = SimulationConfig { rounds, seed: derive_seed(4, index as u64), ..config.clone() };
+        merged.merge(&simulate(&part).unwrap());
+    }
+    assert_eq!(merged, stats);
+    assert_ne!(derive_seed(4, 0), derive_seed(4, 1));
+}
+
+#[test]
+fn dealer_probabilities_test() {
+    let single: Composition = Composition::decks(1);
+    assert_eq!(single.total(), 52);
+    assert_eq!(single.count(10), 16);
+    assert!(Composition::default().without(5).is_none());
+
+    let european: TableRules = TableRules::european();
+    let ace_up: DealerOutcomes = dealer_probabilities(&single.without(11).unwrap(), 11, &european);
+    assert!((ace_up.blackjack() - 16.0 / 51.0).abs() < 1e-12);
+    assert!((ace_up.0.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let rules: TableRules = TableRules::vegas_strip();
+    let peeked: DealerOutcomes = dealer_probabilities(&single.without(10).unwrap(), 10, &rules);
+    assert_eq!(peeked.blackjack(), 0.0);
+    assert!((peeked.0.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    // A large shoe converges on the infinite-deck figures.
+    let shoe: Composition = Composition::decks(64).without(6).unwrap();
+    for hit_soft_17 in [false, true] {
+        let rules: TableRules = TableRules { hit_soft_17, ..TableRules::vegas_strip() };
+        let exact: DealerOutcomes = dealer_probabilities(&shoe, 6, &rules);
+        let infinite: DealerOutcomes = dealer_outcomes(6, &rules);
+        for (exact, infinite) in exact.0.iter().zip(infinite.0.iter()) {
+            assert!((exact - infinite).abs() < 0.002);
+        }
+    }
+
+    // Nothing but four twos behind a ten always leaves the dealer on 18.
+    let mut stacked: Composition = Composition::default();
+    stacked.0[0] = 4;
+    let twos: DealerOutcomes = dealer_probabilities(&stacked, 10, &european);
+    assert_eq!(twos.total(18), 1.0);
+}
+
+#[test]
+fn analyzer_test() {
+    let ten: Card = Card { suit: Suit::Spades, rank: Rank::Ten, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let eight: Card = Card { suit: Suit::Clubs, rank: Rank::Eight, hidden: false };
+    let rules: TableRules = TableRules::atlantic_city();
+    let table: StrategyTable = StrategyTable::new(&rules);
+
+    // A deep shoe agrees with the infinite-deck strategy table.
+    let shoe: Composition = Composition::decks(32).without(10).unwrap().without(10).unwrap().without(6).unwrap();
+    let exact: ActionValues = analyze(&Hand(vec![ten, six], false), 10, &shoe, &rules);
+    let infinite: ActionValues = table.lookup(HandKind::Hard(16), 10).unwrap();
+    assert!((exact.stand - infinite.stand).abs() < 0.01);
+    assert!((exact.hit - infinite.hit).abs() < 0.01);
+    assert!((exact.double - infinite.double).abs() < 0.02);
+    assert_eq!(exact.surrender, Some(-0.5));
+    assert_eq!(exact.split, None);
+
+    let eights: ActionValues = analyze(&Hand(vec![eight, eight], false), 10, &Composition::decks(6).without(8).unwrap().without(8).unwrap(), &rules);
+    assert!(eights.split.unwrap() > eights.hit && eights.split.unwrap() > eights.stand);
+
+    // With only tens left, standing on 16 against a ten is a sure loss and hitting always busts.
+    let mut tens: Composition = Composition::default();
+    tens.0[8] = 20;
+    let stacked: ActionValues = analyze(&Hand(vec![ten, six], false), 10, &tens, &TableRules::vegas_strip());
+    assert_eq!((stacked.stand, stacked.hit, stacked.double), (-1.0, -1.0, -2.0));
+}
+
+#[test]
+fn bust_probability_test() {
+    let ten: Card = Card { suit: Suit::Spades, rank: Rank::Ten, hidden: false };
+    let six: Card = Card { suit: Suit::Hearts, rank: Rank::Six, hidden: false };
+    let sixteen: Hand = Hand(vec![ten, six], false);
+
+    let mut composition: Composition = Composition::default();
+    composition.0[8] = 3;
+    composition.0[0] = 1;
+    assert_eq!(bust_probability(&sixteen, &composition), Some(0.75));
+    assert_eq!(bust_probability(&sixteen, &Composition::default()), None);
+    assert_eq!(bust_probability(&Hand(vec![ten, six, ten], false), &Composition::default()), Some(1.0));
+
+    // The hole card counts as unseen, so the chance doesn't change with what the dealer holds.
+    let rules: TableRules = TableRules::vegas_strip();
+    for hole in [2, 10] {
+        let (engine, _) = seeded_deal(&rules, 10, |engine: &BlackjackEngine| {
+            let stiff: bool = engine.hands()[0].first().is_some_and(|seat_hand: &PlayerHand| !seat_hand.hand.value().soft && seat_hand.hand.sum() >= 12);
+            stiff && can_play(engine, Action::Hit) && hole_card(engine) == hole
+        });
+        let mut unseen: Vec<Card> = engine.shoe().deck().cards().clone();
+        unseen.push(engine.dealer().hand()[1]);
+        let expected: Option<f64> = bust_probability(&engine.hands()[0][0].hand, &Composition::from_cards(&unseen));
+        assert_eq!(seat_bust_probability(&engine, 0, 0), expected);
+        assert_ne!(seat_bust_probability(&engine, 0, 0), bust_probability(&engine.hands()[0][0].hand, &Composition::of(engine.shoe().deck())));
+    }
+}
+
+#[test]
+fn house_edge_test() {
+    let vegas: f64 = house_edge(&TableRules::vegas_strip());
+    assert!(vegas > 0.002 && vegas < 0.008, "{}", vegas);
+
+    let six_to_five: TableRules = TableRules { hit_soft_17: true, blackjack_payout: BlackjackPayout::SixToFive, ..TableRules::vegas_strip() };
+    assert!(house_edge(&six_to_five) - vegas > 0.015);
+
+    for rules in TableRules::presets().into_iter().chain([six_to_five]) {
+        let contributions: Vec<RuleContribution> = rule_contributions(&rules);
+        let total: f64 = contributions.iter().map(|contribution: &RuleContribution| contribution.edge).sum();
+        assert!((total - house_edge(&rules) - deck_count_effect(rules.decks)).abs() < 1e-9);
+    }
+
+    let downtown: Vec<RuleContribution> = rule_contributions(&TableRules::downtown());
+    assert_eq!(downtown.len(), 3);
+    assert_eq!(downtown[1].rule, "Dealer hits soft 17");
+    assert!(downtown[1].edge > 0.001 && downtown[1].edge < 0.004);
+    assert_eq!(downtown[2], RuleContribution { rule: String::from("2 deck(s)"), edge: deck_count_effect(2) });
+
+    // Single deck is about half a percent better for the player than eight decks, and six decks barely differ from eight.
+    assert!((deck_count_effect(1) - deck_count_effect(8) + 0.0048).abs() < 0.0002);
+    assert!((deck_count_effect(6) - deck_count_effect(8) + 0.0002).abs() < 0.0001);
+    assert!(deck_count_effect(8) < 0.0 && deck_count_effect(8) > deck_count_effect(4));
+}
+
+#[test]
+fn betting_test() {
+    let rules: TableRules = TableRules::vegas_strip();
+    let players: Vec<Player> = vec![Player(0, Balance(100_000.into_chips()), Bet(vec![]), true)];
+    let mut engine: BlackjackEngine = BlackjackEngine::new(rules.clone(), Shoe::from_rules_with_seed(&rules, 3), Table(players));
+    let mut bot: BasicStrategyBot = BasicStrategyBot::new(10);
+
+    let mut martingale: Martingale = Martingale::new(10);
+    let mut paroli: Paroli = Paroli::new(10);
+    let mut one_three_two_six: OneThreeTwoSix = OneThreeTwoSix::new(10);
+    let mut d_alembert: DAlembert = DAlembert::new(10);
+    let (mut doubled, mut wins, mut step, mut units) = (10, 0, 0, 1);
+
+    for _ in 0..60 {
+        engine.start_round().unwrap();
+        match engine.last_result(0).map(i64::signum) {
+            Some(1) => (doubled, wins, step, units) = (10, (wins + 1) % 3, (step + 1) % 4, (units - 1).max(1)),
+            Some(-1) => (doubled, wins, step, units) = (doubled * 2, 0, 0, units + 1),
+            _ => {},
+        }
+
+        assert_eq!(paroli.next_bet(&engine, 0), 10 << wins);
+        assert_eq!(one_three_two_six.next_bet(&engine, 0), 10 * [1, 3, 2, 6][step]);
+        assert_eq!(d_alembert.next_bet(&engine, 0), 10 * units);
+        let bet: u32 = martingale.next_bet(&engine, 0);
+        assert_eq!(bet, doubled);
+        // Asking again in the same round, as after a rejected bet, doesn't advance the progressions.
+        assert_eq!(martingale.next_bet(&engine, 0), bet);
+        assert_eq!(paroli.next_bet(&engine, 0), 10 << wins);
+
+        engine.apply(Action::Bet(bet)).unwrap();
+        while let Some(decision) = engine.decision() {
+            let action: Action = bot.decide(&engine, &decision);
+            engine.apply(action).unwrap();
+        }
+    }
+
+    // Off the top of the shoe there is no advantage, so Kelly sticks to the minimum.
+    let fresh: BlackjackEngine = BlackjackEngine::new(rules.clone(), Shoe::from_rules_with_seed(&rules, 3), Table(vec![Player(0, Balance(100_000.into_chips()), Bet(vec![]), true)]));
+    assert_eq!(Kelly::new(10, 0.5).next_bet(&fresh, 0), 10);
+    assert_eq!(FlatBet(25).next_bet(&fresh, 0), 25);
+
+    assert_eq!(betting_strategy_by_name("dalembert", 5).unwrap().name(), "D'Alembert");
+    assert_eq!(betting_strategy_by_name("1-3-2-6", 5).unwrap().name(), "1-3-2-6");
+    assert!(betting_strategy_by_name("fibonacci", 5).is_none());
+    assert_eq!(betting_strategy_by_name("Count Ramp", 5).unwrap().name(), "Count ramp");
+    assert_eq!(betting_strategy_by_name("kelly", 5).unwrap().name(), "Kelly (50%)");
+    assert!(["", "k", "c", "mart"].iter().all(|name: &&str| betting_strategy_by_name(name, 5).is_none()));
+}
+
+#[test]
+fn bankroll_test() {
+    let profile: RiskProfile = RiskProfile { mean: 0.02, standard_deviation: 2.0, unit: 10 };
+    assert!((profile.n0().unwrap() - 10_000.0).abs() < 1e-6);
+    assert!((profile.score() - 100.0).abs() < 1e-6);
+    assert!((profile.hourly_win(100) - 20.0).abs() < 1e-9);
+    assert!((profile.hourly_standard_deviation(100) - 200.0).abs() < 1e-9);
+
+    // exp(-2 * 0.02 * 1000 / 4) = exp(-10)
+    assert!((profile.risk_of_ruin(10_000) - (-10.0f64).exp()).abs() < 1e-12);
+    let bankroll: u32 = profile.required_bankroll(0.05).unwrap();
+    assert!((profile.risk_of_ruin(bankroll) - 0.05).abs() < 1e-3);
+
+    let losing: RiskProfile = RiskProfile::flat(&TableRules::vegas_strip(), 10);
+    assert_eq!(losing.risk_of_ruin(1_000_000), 1.0);
+    assert_eq!((losing.n0(), losing.required_bankroll(0.05), losing.score()), (None, None, 0.0));
+
+    let config: SimulationConfig = SimulationConfig { betting: Some(String::from("1-3-2-6")), ..SimulationConfig::new(TableRules::vegas_strip(), 500, 2) };
+    let stats: SimulationStats = simulate(&config).unwrap();
+    assert!(stats.initial_wagered > 500 * 10);
+    assert_eq!(RiskProfile::from_stats(&stats).unit, 10);
+}
+
+#[test]
+fn table_limits_test() {
+    let limits: TableLimits = TableLimits::new(10, 500, 5);
+    assert!(limits.validate(25, 100).is_ok());
+    assert!(matches!(limits.validate(200, 100), Err(DeckError::InsufficientBalance)));
+    assert!(matches!(limits.validate(5, 100), Err(DeckError::BelowTableMinimum(10))));
+    assert!(matches!(limits.validate(0, 100), Err(DeckError::BelowTableMinimum(10))));
+    assert!(matches!(limits.validate(600, 1000), Err(DeckError::AboveTableMaximum(500))));
+    assert!(matches!(limits.validate(23, 100), Err(DeckError::InvalidBetIncrement(5))));
+    assert_eq!(limits.to_string(), "10-500 in steps of 5");
+
+    assert_eq!(limits.clamp(23, 100), Some(20));
+    assert_eq!(limits.clamp(1, 100), Some(10));
+    assert_eq!(limits.clamp(2000, 1000), Some(500));
+    assert_eq!(limits.clamp(50, 33), Some(30));
+    assert_eq!(limits.clamp(50, 9), None);
+
+    for below_minimum in [BelowMinimum::Bankrupt, BelowMinimum::SitOut] {
+        let rules: TableRules = TableRules { limits, below_minimum, ..TableRules::default() };
+        let players: Table = Table(vec![
+            Player(0, Balance(7.into_chips()), Bet(vec![]), true),
+            Player(1, Balance(100.into_chips()), Bet(vec![]), true),
+        ]);
+        let mut engine: BlackjackEngine = BlackjackEngine::new(rules.clone(), Shoe::from_rules_with_seed(&rules, 3), players);
+
+        let events: Vec<Event> = engine.start_round().unwrap();
+        assert_eq!(engine.decision(), Some(Decision::Bet { seat: 1, balance: 100 }));
+        match below_minimum {
+            BelowMinimum::Bankrupt => {
+                assert!(events.contains(&Event::Bankrupt { seat: 0 }));
+                assert!(!engine.players().0[0].is_active());
+            },
+            BelowMinimum::SitOut => {
+                assert!(events.contains(&Event::SatOut { seat: 0 }));
+                assert!(engine.players().0[0].is_active());
+            },
+        }
+
+        assert!(matches!(engine.apply(Action::Bet(13)), Err(DeckError::InvalidBetIncrement(5))));
+        let mut script: ScriptedController = ScriptedController::parse("bet 23\n").unwrap();
+        assert_eq!(script.decide_bet(&engine, 1, 100), 20);
+        assert_eq!(script.decide_bet(&engine, 1, 100), 10);
+        let mut script: ScriptedController = ScriptedController::parse("hit\ninsurance 50\n").unwrap();
+        assert_eq!(script.decide_bet(&engine, 1, 100), 10);
+        assert_eq!(script.decide_insurance(&engine, 1, 5), 0);
+        assert_eq!(script.remaining(), 2);
+        assert_eq!(script.decide_action(&engine, 1, 0, &[Action::Stand]), Action::Stand);
+        assert_eq!(script.decide_action(&engine, 1, 0, &[Action::Hit, Action::Stand]), Action::Hit);
+        assert_eq!(script.decide_insurance(&engine, 1, 5), 5);
+        assert_eq!(script.remaining(), 0);
+        engine.apply(Action::Bet(20)).unwrap();
+        while let Some(decision) = engine.decision() {
+            let action: Action = match decision {
+                Decision::Play { .. } => Action::Stand,
+                _ => Action::Decline,
+            };
+            engine.apply(action).unwrap();
+        }
+        assert!(engine.hands()[0].is_empty());
+        assert_eq!(engine.hands()[1].len(), 1);
+    }
+}
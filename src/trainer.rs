@@ -0,0 +1,129 @@
+use std::io::Write;
+use std::time::{Duration, Instant};
+use rand::prelude::*;
+use deckbuilder::prelude::*;
+use crate::human::read_response;
+use crate::{option_value, parse_option};
+
+fn parse_system(args: &[String]) -> Result<Box<dyn CountingSystem>, String> {
+    match option_value(args, "--system") {
+        None => Ok(Box::new(HiLo)),
+        Some(name) => system_by_name(name).ok_or_else(|| format!("unknown counting system '{}'", name)),
+    }
+}
+
+fn ask_count(question: &str) -> (Option<f64>, Duration) {
+    println!("\x1b[1;33m{}\x1b[0m", question);
+    let asked: Instant = Instant::now();
+    let answer: Option<f64> = read_response().parse::<f64>().ok();
+    (answer, asked.elapsed())
+}
+
+fn show_card(card: &Card, speed: Duration) {
+    print!("\r{}      ", card);
+    let _ = std::io::stdout().flush();
+    std::thread::sleep(speed);
+}
+
+/// `train [--system <name>] [--decks <n>] [--speed <ms>] [--cards <n>] [--seed <n>]` deals cards one at a time and
+/// stops at random checkpoints to ask for the running and true count.
+pub fn run_trainer(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let system: Box<dyn CountingSystem> = parse_system(args)?;
+    let decks: u32 = parse_option(args, "--decks", 1)?.max(1);
+    let speed: Duration = Duration::from_millis(parse_option(args, "--speed", 1000)?);
+    let seed: u64 = parse_option(args, "--seed", rand::random())?;
+    let mut rng: StdRng = StdRng::seed_from_u64(seed);
+
+    let mut deck: Deck = Deck::build(decks);
+    deck.shuffle_with(&mut rng);
+    let cards: u32 = parse_option(args, "--cards", deck.total_cards())?.min(deck.total_cards());
+
+    println!("\x1b[1;34m### Counting Trainer ###\x1b[0m");
+    println!("\x1b[1;34mSystem:\x1b[0m {}\t\x1b[1;34mDecks:\x1b[0m {}\t\x1b[1;34mSeed:\x1b[0m {}", system.name(), decks, seed);
+    println!("Press Enter to start.");
+    read_response();
+
+    let mut counter: Counter = Counter::new(system, decks);
+    let mut running: Score = Score::default();
+    let mut true_count: Score = Score::default();
+    let mut checkpoints: Checkpoints = Checkpoints::new(cards, &mut rng);
+
+    for dealt in 1..=cards {
+        let card: Card = match deck.draw() {
+            Ok(card) => card,
+            Err(_) => break,
+        };
+        show_card(&card, speed);
+        counter.count(&card);
+
+        if !checkpoints.reached(dealt, &mut rng) {
+            continue;
+        }
+        println!();
+
+        let (answer, took) = ask_count("Running count?");
+        let correct: bool = running_count_correct(answer, &counter);
+        running.record(correct, took);
+        match correct {
+            true => println!("\x1b[1;32mCorrect!\x1b[0m"),
+            false => println!("\x1b[1;31mThe running count is {}\x1b[0m", counter.running_count()),
+        }
+
+        if counter.system().is_balanced() {
+            let remaining: f64 = deck.total_cards() as f64 / 52.0;
+            let (answer, took) = ask_count(&format!("True count? ({:.1} deck(s) left)", remaining));
+            let exact: f64 = counter.true_count(remaining);
+            let correct: bool = true_count_correct(answer, exact);
+            true_count.record(correct, took);
+            match correct {
+                true => println!("\x1b[1;32mCorrect!\x1b[0m"),
+                false => println!("\x1b[1;31mThe true count is {:.1}\x1b[0m", exact),
+            }
+        }
+    }
+
+    println!();
+    println!("\x1b[1;34m### Session Results ###\x1b[0m");
+    println!("\x1b[1;34mRunning count:\x1b[0m\t{}", running);
+    if true_count.questions > 0 {
+        println!("\x1b[1;34mTrue count:\x1b[0m\t{}", true_count);
+    }
+
+    Ok(())
+}
+
+/// `train drill [--system <name>] [--seed <n>]` times counting down a single deck, one card per Enter.
+pub fn run_drill(args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let system: Box<dyn CountingSystem> = parse_system(args)?;
+    let seed: u64 = parse_option(args, "--seed", rand::random())?;
+
+    let mut deck: Deck = Deck::build(1);
+    deck.shuffle_with_seed(seed);
+
+    println!("\x1b[1;34m### Count Down a Deck ###\x1b[0m");
+    println!("\x1b[1;34mSystem:\x1b[0m {}\t\x1b[1;34mSeed:\x1b[0m {}", system.name(), seed);
+    println!("Press Enter to turn over each card. The clock starts with the first card.");
+    read_response();
+
+    let mut counter: Counter = Counter::new(system, 1);
+    let started: Instant = Instant::now();
+
+    while let Ok(card) = deck.draw() {
+        print!("{}", card);
+        let _ = std::io::stdout().flush();
+        counter.count(&card);
+        read_response();
+    }
+
+    // The clock stops with the last card, so typing the answer doesn't count against the speed.
+    let elapsed: Duration = started.elapsed();
+    let (answer, _) = ask_count("Final running count?");
+
+    match running_count_correct(answer, &counter) {
+        true => println!("\x1b[1;32mCorrect!\x1b[0m"),
+        false => println!("\x1b[1;31mThe final running count is {}\x1b[0m", counter.running_count()),
+    }
+    println!("\x1b[1;34mTime:\x1b[0m {:.1}s ({:.2}s per card)", elapsed.as_secs_f64(), elapsed.as_secs_f64() / 52.0);
+
+    Ok(())
+}
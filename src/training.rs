@@ -0,0 +1,83 @@
+use std::time::Duration;
+use rand::Rng;
+use crate::counting::Counter;
+
+/// Answers and time taken over one training session.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Score {
+    pub questions: u32,
+    pub correct: u32,
+    pub answer_time: Duration,
+}
+
+impl Score {
+    pub fn record(&mut self, correct: bool, took: Duration) {
+        self.questions += 1;
+        self.correct += correct as u32;
+        self.answer_time += took;
+    }
+
+    pub fn accuracy(&self) -> Option<f64> {
+        match self.questions {
+            0 => None,
+            questions => Some(self.correct as f64 / questions as f64),
+        }
+    }
+
+    pub fn average_time(&self) -> Option<Duration> {
+        match self.questions {
+            0 => None,
+            questions => Some(self.answer_time / questions),
+        }
+    }
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.accuracy(), self.average_time()) {
+            (Some(accuracy), Some(time)) => write!(
+                f,
+                "Accuracy: {}/{} ({:.0}%)\tAverage answer time: {:.1}s",
+                self.correct,
+                self.questions,
+                accuracy * 100.0,
+                time.as_secs_f64(),
+            ),
+            _ => write!(f, "No checkpoints reached."),
+        }
+    }
+}
+
+/// Where the trainer stops to ask for the count: every 5 to 15 cards, and always on the last card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoints {
+    next: u32,
+    last: u32,
+}
+
+impl Checkpoints {
+    pub const SPACING: std::ops::RangeInclusive<u32> = 5..=15;
+
+    pub fn new(last: u32, rng: &mut impl Rng) -> Self {
+        Self { next: rng.gen_range(Self::SPACING), last }
+    }
+
+    /// Whether the `dealt`th card is a checkpoint, moving on to the next one if it is.
+    pub fn reached(&mut self, dealt: u32, rng: &mut impl Rng) -> bool {
+        if dealt != self.next && dealt != self.last {
+            return false;
+        }
+        self.next += rng.gen_range(Self::SPACING);
+        true
+    }
+}
+
+/// The running count has to be exact.
+pub fn running_count_correct(answer: Option<f64>, counter: &Counter) -> bool {
+    answer == Some(counter.running_count())
+}
+
+/// A true count within half a point of `exact` is close enough to size a bet or play an index.
+pub fn true_count_correct(answer: Option<f64>, exact: f64) -> bool {
+    answer.is_some_and(|answer: f64| (answer - exact).abs() <= 0.5)
+}
@@ -1,104 +1,72 @@
 use std::cmp::Ordering;
 use deckbuilder::prelude::*;
 
-#[derive(Clone)]
-pub struct Player(pub u32, pub Balance, pub Bet, pub bool);
-
-impl Player {
-    pub fn get_balance(&self) -> u32 {
-        self.1.sum()
-    }
-
-    pub fn is_active(&self) -> bool {
-        self.3
-    }
-
-    pub fn bankrupt(&mut self) {
-        self.3 = false
-    }
+pub struct HandHistory {
+    pub seed: u64,
+    pub rounds: Vec<String>,
 }
 
-pub fn game_over(players: &Vec<Player>) -> bool {
-    let total_players: u32 = players.len() as u32;
-    let mut total_players_bankrupt: u32 = 0;
-
-    for player in players {
-        if !player.is_active() {
-            total_players_bankrupt += 1;
-        }
+impl HandHistory {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, rounds: Vec::new() }
     }
 
-    total_players_bankrupt == total_players
-}
-
-pub struct Table(pub Vec<Player>);
+    pub fn record(&mut self, round: u64, dealer: &Dealer, player_hands: &[Vec<PlayerHand>], settlements: &[Vec<Settlement>]) {
+        let mut entry: String = format!("Round {} (seed: {})\n  Dealer: {}\n", round, self.seed, dealer);
 
-impl Table {
-    pub fn get_player_by_id(&self, id: u32) -> Option<Player> {
-        self.0.iter().find(|player: &&Player| player.0 == id).cloned()
-    }
+        for (player, (seat_hands, seat_settlements)) in player_hands.iter().zip(settlements).enumerate() {
+            for (hand_index, seat_hand) in seat_hands.iter().enumerate() {
+                if let Some(settlement) = seat_settlements.get(hand_index) {
+                    entry.push_str(&format!(
+                        "  Player {} ({}): {}  {} {:+}\n",
+                        player + 1, hand_index + 1, seat_hand.hand, settlement.outcome, settlement.net
+                    ));
+                }
+            }
+        }
 
-    pub fn get_mut_player_by_id(&mut self, id: u32) -> Option<&mut Player> {
-        self.0.iter_mut().find(|player: &&mut Player| player.0 == id)
+        self.rounds.push(entry);
     }
 }
 
-pub fn show_results(players: &mut Table, player_hands: &Vec<Hand>, dealer_hand: &Hand) -> Result<(), DeckError> {
-    let dealer_busted: bool = dealer_hand.busted();
-    
-    for (index, player_hand) in player_hands.iter().enumerate() {
-        
-        if !players.0[index].is_active() {
-            continue;
+impl std::fmt::Display for HandHistory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "\x1b[1;34m### Hand History (seed: {}) ###\x1b[0m", self.seed)?;
+        writeln!(f, "Replay this session with '--seed {}'", self.seed)?;
+        for round in &self.rounds {
+            write!(f, "{}", round)?;
         }
+        Ok(())
+    }
+}
 
-        if let Some(player_instance) = players.get_mut_player_by_id(index as u32) {
-            
-            if player_hand.busted() {
-                println!("\x1b[1;34mPlayer {}:\x1b[0m\tBusted!\t\x1b[1;31m-{}\x1b[0m", 
-                    index + 1, 
-                    player_instance.2.sum()
-                );
-                player_instance.1.0 = (player_instance.1.sum() - player_instance.2.sum()).into_chips();
-                continue;
-            }
-
-            if dealer_busted {
-                println!(
-                    "\x1b[1;34mPlayer {}:\x1b[0m\tWin! (Dealer Busted)\t\x1b[1;32m{}\x1b[0m",
-                    index + 1,
-                    player_instance.2.sum() * 2
-                );
-                player_instance.1.0 = (player_instance.1.sum() + player_instance.2.sum() * 2).into_chips();
-                continue;
-            }
-
-            if player_hand.is_blackjack() {
-                println!("\x1b[1;34mPlayer {}:\x1b[0m\tBlackjack!\t\x1b[1;32m{}\x1b[0m", 
-                    index + 1,
-                    player_instance.2.sum() * 2
-                );
-            }
+pub fn show_results(settlements: &[Vec<Settlement>]) {
+    for (index, seat_settlements) in settlements.iter().enumerate() {
+        let hands: usize = seat_settlements
+            .iter()
+            .filter(|settlement: &&Settlement| settlement.outcome != Outcome::Insurance)
+            .count();
+
+        for (hand_index, settlement) in seat_settlements.iter().enumerate() {
+            let label: String = match settlement.outcome {
+                Outcome::Insurance => format!("\x1b[1;34mPlayer {} (Insurance):\x1b[0m", index + 1),
+                _ => seat_label(index, hand_index, hands),
+            };
 
-            else {
-                match dealer_hand.compare_to(&player_hand)? {
-                    Ordering::Equal => {
-                        println!("\x1b[1;34mPlayer {}:\x1b[0m\tPush!\t\x1b[1;32m{}\x1b[0m", index + 1, player_instance.2.sum());
-                    },
-                    Ordering::Less => {
-                        println!("\x1b[1;34mPlayer {}:\x1b[0m\tWin!\t\x1b[1;32m{}\x1b[0m", index + 1, player_instance.2.sum() * 2);
-                        player_instance.1.0 = (player_instance.1.sum() + player_instance.2.sum() * 2).into_chips();     
-                    },
-                    Ordering::Greater => {
-                        println!("\x1b[1;34mPlayer {}:\x1b[0m\tLoss!\t\x1b[1;31m-{}\x1b[0m", index + 1, player_instance.2.sum());
-                        player_instance.1.0 = (player_instance.1.sum() - player_instance.2.sum()).into_chips();
-                    }
-                }
+            match settlement.net.cmp(&0) {
+                Ordering::Greater => println!("{}\t{}\t\x1b[1;32m+{}\x1b[0m", label, settlement.outcome, settlement.net),
+                Ordering::Equal => println!("{}\t{}\t\x1b[1;32m{}\x1b[0m", label, settlement.outcome, settlement.wager),
+                Ordering::Less => println!("{}\t{}\t\x1b[1;31m{}\x1b[0m", label, settlement.outcome, settlement.net),
             }
         }
     }
+}
 
-    Ok(())
+fn seat_label(player: usize, hand_index: usize, total_hands: usize) -> String {
+    match total_hands > 1 {
+        true => format!("\x1b[1;34mPlayer {} (Hand {}):\x1b[0m", player + 1, hand_index + 1),
+        false => format!("\x1b[1;34mPlayer {}:\x1b[0m", player + 1),
+    }
 }
 
 pub fn show_final_results(index: usize, player: &Player) {
@@ -116,68 +84,92 @@ pub fn show_final_results(index: usize, player: &Player) {
     );
 }
 
-pub fn show_player_hands(table: &Table, player_hands: &Vec<Hand>, active_player: u32) {
-    for (player, hand) in player_hands.iter().enumerate() {
-        if !table.0[player].is_active() {
-            continue;
-        }
-        if player == active_player as usize {
-            println!("\x1b[1;34mplayer {}: {}\x1b[0m", player + 1, hand);
-        } else {
-            println!("player {}: {}", player + 1, hand);
+pub fn show_player_hands(player_hands: &[Vec<PlayerHand>], active: Option<(usize, usize)>) {
+    for (player, seat_hands) in player_hands.iter().enumerate() {
+        for (hand_index, seat_hand) in seat_hands.iter().enumerate() {
+            let name: String = match seat_hands.len() > 1 {
+                true => format!("player {} ({})", player + 1, hand_index + 1),
+                false => format!("player {}", player + 1),
+            };
+            if active == Some((player, hand_index)) {
+                println!("\x1b[1;34m{}: {}\x1b[0m ({})", name, seat_hand.hand, seat_hand.hand.value());
+            } else {
+                println!("{}: {} ({})", name, seat_hand.hand, seat_hand.hand.value());
+            }
         }
     }
 }
 
-pub fn show_dealer_hand(dealer_hand: &Hand) {
-    print!("\n");
+pub fn show_dealer_hand(dealer: &Dealer) {
+    println!();
     println!("Dealer Cards:");
-    println!("{} {}", dealer_hand[0], dealer_hand[1]);
-    print!("\n");
+    println!("{}", dealer);
+    println!();
 }
 
-pub fn busting_probability(deck: &Deck, hand: &Hand) -> Result<f64, DeckError> {
-        let hand_value: u32 = hand.level_off_ace();
+fn play_color(play: Play) -> &'static str {
+    match play {
+        Play::Hit => "\x1b[1;37;41m",
+        Play::Stand => "\x1b[1;30;43m",
+        Play::Double => "\x1b[1;37;44m",
+        Play::DoubleOrStand => "\x1b[1;30;46m",
+        Play::Split => "\x1b[1;30;42m",
+        Play::SurrenderOrHit | Play::SurrenderOrStand | Play::SurrenderOrSplit => "\x1b[1;37;45m",
+    }
+}
 
-        if hand_value > 21 {
-            return Ok(1.0);
+pub fn show_chart(chart: &Chart) {
+    let up_cards: [&str; 10] = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "A"];
+
+    for (title, rows) in [
+        ("Hard", (5..=21).map(HandKind::Hard).collect::<Vec<HandKind>>()),
+        ("Soft", (13..=21).map(HandKind::Soft).collect()),
+        ("Pairs", (2..=11).map(HandKind::Pair).collect()),
+    ] {
+        println!();
+        print!("\x1b[1;34m{:<6}\x1b[0m", title);
+        for up_card in up_cards {
+            print!("{:^4}", up_card);
         }
-
-        let mut busting_cards: i32 = 0;
-        let total_cads: f64 = deck.total_cards() as f64;
-
-        for card in deck.cards() {
-            let potential_sum: u32 = hand_value + card.value();
-            let adjusted_sum: u32 = if hand.contains(Rank::Ace) && potential_sum > 21 {
-                potential_sum - 10
-            } else {
-                potential_sum
-            };
-
-            if adjusted_sum > 21 {
-                busting_cards += 1;
+        println!();
+
+        for kind in rows {
+            print!("{:<6}", row_label(kind));
+            for up_card in 2..=11 {
+                match chart.play(kind, up_card) {
+                    Some(play) => print!("{}{:^4}\x1b[0m", play_color(play), play.code()),
+                    None => print!("{:^4}", "-"),
+                }
             }
+            println!();
         }
+    }
 
-        Ok(busting_cards as f64 / total_cads)
+    println!();
+    println!(
+        "{} H \x1b[0m Hit  {} S \x1b[0m Stand  {} D \x1b[0m Double/Hit  {} Ds \x1b[0m Double/Stand  {} P \x1b[0m Split  {} R \x1b[0m Surrender",
+        play_color(Play::Hit), play_color(Play::Stand), play_color(Play::Double),
+        play_color(Play::DoubleOrStand), play_color(Play::Split), play_color(Play::SurrenderOrHit),
+    );
 }
 
-pub fn dealer_logic<'a>(deck: &'a mut Deck, dealer_hand: &'a mut Hand, hit_on_soft_17: bool) -> Result<&'a mut Hand, DeckError> {
-    loop {
-        let total: u32 = dealer_hand.sum();
-        let is_soft: bool = dealer_hand.contains(Rank::Ace) && total <= 21 && total - 10 > 0 && total - 10 <= 21;
-
-        if total > 21 {
-            dealer_hand.1 = true;
-            break;
-        }
-
-        if total > 17 || (total == 17 && (!hit_on_soft_17 || is_soft)) {
-            break;
-        }
-
-        dealer_hand.draw_from(deck)?;
+pub fn show_deviations(deviations: &[Deviation]) {
+    if deviations.is_empty() {
+        println!("\x1b[1;32mThe chart matches basic strategy.\x1b[0m");
+        return;
     }
 
-    Ok(dealer_hand)
-}
\ No newline at end of file
+    let total: f64 = deviations.iter().map(|deviation: &Deviation| deviation.cost).sum();
+    println!("\x1b[1;31m{} deviation(s) from basic strategy:\x1b[0m", deviations.len());
+    for deviation in deviations {
+        let up_card: String = match deviation.up_card {
+            11 => String::from("A"),
+            up_card => up_card.to_string(),
+        };
+        println!(
+            "  {:<4} vs {:<3} chart: {:<3} optimal: {:<3} cost: {:.4}",
+            row_label(deviation.kind), up_card, deviation.play.code(), deviation.optimal.code(), deviation.cost
+        );
+    }
+    println!("Total cost across the listed cells: {:.4} units", total);
+}